@@ -0,0 +1,58 @@
+use compgeo::{
+    algorithms::{chan, graham_scan, monotone_chain},
+    data::{pos2, Pos2},
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Points in a disk, which have a large hull.
+fn disk(rng: &mut StdRng, n: usize) -> Vec<Pos2<f64>> {
+    (0..n)
+        .map(|_| {
+            let (r, angle) = (rng.gen_range(0.0..1.0f64).sqrt(), rng.gen_range(0.0..6.3));
+            pos2(r * f64::cos(angle), r * f64::sin(angle))
+        })
+        .collect()
+}
+
+/// Points in a triangle, which have a tiny hull.
+fn triangle(rng: &mut StdRng, n: usize) -> Vec<Pos2<f64>> {
+    (0..n)
+        .map(|_| {
+            let (x, y) = (rng.gen_range(0.0..1.0f64), rng.gen_range(0.0..1.0f64));
+            if x + y > 1.0 {
+                pos2(1.0 - x, 1.0 - y)
+            } else {
+                pos2(x, y)
+            }
+        })
+        .chain([pos2(0.0, 0.0), pos2(1.0, 0.0), pos2(0.0, 1.0)])
+        .collect()
+}
+
+fn hulls(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    for (name, points) in [
+        ("disk", disk as fn(&mut StdRng, usize) -> Vec<Pos2<f64>>),
+        ("triangle", triangle),
+    ] {
+        let mut group = c.benchmark_group(name);
+        group.sample_size(10);
+        for n in [10_000, 100_000, 1_000_000] {
+            let points = points(&mut rng, n);
+            group.bench_with_input(BenchmarkId::new("graham_scan", n), &points, |b, p| {
+                b.iter(|| graham_scan(p))
+            });
+            group.bench_with_input(BenchmarkId::new("monotone_chain", n), &points, |b, p| {
+                b.iter(|| monotone_chain(p, false))
+            });
+            group.bench_with_input(BenchmarkId::new("chan", n), &points, |b, p| {
+                b.iter(|| chan(p))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, hulls);
+criterion_main!(benches);
@@ -0,0 +1,527 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::data::{
+    simplex::{Lexicographic, LineSegment, Point, SegmentIntersection},
+    Scalar,
+};
+
+/// A point where two or more segments meet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Intersection<T: Scalar = f32> {
+    pub point: Point<T>,
+    /// The indices of the segments through the point, in increasing order.
+    pub segments: Vec<usize>,
+}
+
+/// An event handled by the sweep, for visualizing the algorithm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepStep<T: Scalar = f32> {
+    /// The event point, which the sweep line passes through.
+    pub position: Point<T>,
+    /// The number of intersections found so far, including the one at `position`.
+    pub found: usize,
+}
+
+// https://en.wikipedia.org/wiki/Bentley%E2%80%93Ottmann_algorithm
+//
+// Finds every point where segments meet in O((n + k) log n) time, for k such points.
+// A vertical line sweeps over the segments from left to right (and, along a vertical
+// line, from the bottom up), keeping the segments it crosses in order. Only segments
+// that are next to each other in that order can meet next, so only they are tested.
+//
+// Segments touching at an endpoint meet there, and segments that overlap meet at both
+// ends of the overlap. Segments of zero length are ignored. With floats the points
+// where segments cross are rounded, so several segments crossing at the same point can
+// be reported as meeting at a few points very close to each other.
+pub fn bentley_ottmann<T: Scalar>(segments: &[LineSegment<T>]) -> Vec<Intersection<T>> {
+    sweep(segments, |_| {})
+}
+
+/// Like [`bentley_ottmann`], but also returns every event the sweep handled.
+pub fn bentley_ottmann_steps<T: Scalar>(
+    segments: &[LineSegment<T>],
+) -> (Vec<Intersection<T>>, Vec<SweepStep<T>>) {
+    let mut steps = vec![];
+    let intersections = sweep(segments, |step| steps.push(step));
+    (intersections, steps)
+}
+
+fn sweep<T: Scalar>(
+    segments: &[LineSegment<T>],
+    mut on_step: impl FnMut(SweepStep<T>),
+) -> Vec<Intersection<T>> {
+    // Every segment runs from its first endpoint the sweep comes across.
+    let segments = segments
+        .iter()
+        .map(|s| {
+            let [a, b] = s.vertices;
+            match a.lexicographic(&b) {
+                Ordering::Greater => [b, a],
+                _ => [a, b],
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // The event points, along with segments known to pass through them. Segments
+    // through an event point can also be found in the status once the sweep gets
+    // there, but a rounded crossing point won't lie exactly on its segments.
+    let mut queue = BTreeMap::new();
+    for (i, [a, b]) in segments.iter().enumerate() {
+        if a.lexicographic(b) != Ordering::Equal {
+            queue
+                .entry(Lexicographic(*a))
+                .or_insert_with(Vec::new)
+                .push(i);
+            queue
+                .entry(Lexicographic(*b))
+                .or_insert_with(Vec::new)
+                .push(i);
+        }
+    }
+
+    let mut status = Status::new();
+    let mut nodes = vec![None; segments.len()];
+    let mut intersections = vec![];
+    while let Some((Lexicographic(p), mut meeting)) = queue.pop_first() {
+        // The segments through p are next to each other in the status.
+        let mut node = status.lower_bound(|s| side(segments[s], p) == Ordering::Greater);
+        while let Some(n) = node {
+            let s = status.segment(n);
+            if side(segments[s], p) != Ordering::Equal {
+                break;
+            }
+            meeting.push(s);
+            node = status.next(n);
+        }
+        meeting.sort_unstable();
+        meeting.dedup();
+        if meeting.len() > 1 {
+            intersections.push(Intersection {
+                point: p,
+                segments: meeting.clone(),
+            });
+        }
+        on_step(SweepStep {
+            position: p,
+            found: intersections.len(),
+        });
+
+        // Take every segment through p out of the status, and put back the ones that
+        // carry on past it, in the order they leave p in.
+        for &s in &meeting {
+            if let Some(n) = nodes[s].take() {
+                status.remove(n);
+            }
+        }
+        let continuing = meeting
+            .iter()
+            .copied()
+            .filter(|&s| segments[s][1].lexicographic(&p) == Ordering::Greater)
+            .collect::<Vec<_>>();
+        for &s in &continuing {
+            let order = |t: usize| {
+                if meeting.binary_search(&t).is_ok() {
+                    leaving(segments[s], segments[t]).then(s.cmp(&t))
+                } else {
+                    side(segments[t], p)
+                }
+            };
+            nodes[s] = Some(status.insert(s, order));
+        }
+
+        // Segments that just became neighbours might meet further on.
+        let mut check = |below: Option<usize>, above: Option<usize>| {
+            let (Some(below), Some(above)) = (below, above) else {
+                return;
+            };
+            let [s, t] = [below, above].map(|n| status.segment(n));
+            if let Some(q) = crossing(segments[s], segments[t]) {
+                if q.lexicographic(&p) == Ordering::Greater {
+                    queue
+                        .entry(Lexicographic(q))
+                        .or_insert_with(Vec::new)
+                        .extend([s, t]);
+                }
+            }
+        };
+        if continuing.is_empty() {
+            let above = status.lower_bound(|s| side(segments[s], p) == Ordering::Greater);
+            let below = above.map_or_else(|| status.last(), |n| status.prev(n));
+            check(below, above);
+        } else {
+            for &s in &continuing {
+                let n = nodes[s];
+                let [below, above] = [status.prev(n.unwrap()), status.next(n.unwrap())];
+                let outside =
+                    |m: Option<usize>| m.is_some_and(|m| !continuing.contains(&status.segment(m)));
+                if outside(below) {
+                    check(below, n);
+                }
+                if outside(above) {
+                    check(n, above);
+                }
+            }
+        }
+    }
+    intersections
+}
+
+/// Which side of the segment p is on: `Greater` above it, `Less` below it, and `Equal`
+/// on it. The segment has to span p along the sweep.
+fn side<T: Scalar>([a, b]: [Point<T>; 2], p: Point<T>) -> Ordering {
+    if a.x == b.x {
+        // A vertical segment, which is only swept over at p's x-coordinate.
+        if p.y > b.y {
+            Ordering::Greater
+        } else if p.y < a.y {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        }
+    } else {
+        T::orient2d(a.into(), b.into(), p.into())
+    }
+}
+
+/// How two segments leaving the same point are ordered, by the angle they leave it at:
+/// `Greater` if the first one goes above the second one. Vertical segments go above
+/// everything else.
+fn leaving<T: Scalar>([a, b]: [Point<T>; 2], [c, d]: [Point<T>; 2]) -> Ordering {
+    let zero = [T::zero(); 2];
+    T::orient2d(zero, (d - c).into(), (b - a).into())
+}
+
+/// The point where two segments meet, if they meet at just one. Segments that overlap
+/// meet at endpoints, which the sweep stops at anyway.
+fn crossing<T: Scalar>(s: [Point<T>; 2], t: [Point<T>; 2]) -> Option<Point<T>> {
+    let [s, t] = [s, t].map(|vertices| LineSegment { vertices });
+    match s.intersection_point(&t) {
+        SegmentIntersection::Point(p) => Some(p),
+        SegmentIntersection::None | SegmentIntersection::Segment(_) => None,
+    }
+}
+
+/// The segments crossing the sweep line, from the bottom up, kept in a treap.
+///
+/// Segments are only compared while they are inserted: after that a segment is found
+/// by its node, which stays put, rather than by searching for it. Searching would
+/// need the order of the segments at the current sweep position, which rounding can
+/// disagree with once segments get close.
+struct Status {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+    rng: StdRng,
+}
+
+struct Node {
+    segment: usize,
+    priority: u64,
+    parent: Option<usize>,
+    children: [Option<usize>; 2],
+}
+
+impl Status {
+    fn new() -> Self {
+        Self {
+            nodes: vec![],
+            root: None,
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+
+    fn segment(&self, node: usize) -> usize {
+        self.nodes[node].segment
+    }
+
+    /// Adds `segment` where `order` puts it: it takes another segment, and returns how
+    /// `segment` compares to it.
+    fn insert(&mut self, segment: usize, order: impl Fn(usize) -> Ordering) -> usize {
+        let (mut parent, mut side) = (None, 0);
+        let mut current = self.root;
+        while let Some(c) = current {
+            parent = Some(c);
+            side = usize::from(order(self.nodes[c].segment) != Ordering::Less);
+            current = self.nodes[c].children[side];
+        }
+
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            segment,
+            priority: self.rng.gen(),
+            parent,
+            children: [None; 2],
+        });
+        match parent {
+            Some(parent) => self.nodes[parent].children[side] = Some(node),
+            None => self.root = Some(node),
+        }
+        while let Some(parent) = self.nodes[node].parent {
+            if self.nodes[parent].priority >= self.nodes[node].priority {
+                break;
+            }
+            self.rotate_up(node);
+        }
+        node
+    }
+
+    fn remove(&mut self, node: usize) {
+        // Rotate the node down until it has at most one child, and splice it out.
+        while let [Some(left), Some(right)] = self.nodes[node].children {
+            if self.nodes[left].priority > self.nodes[right].priority {
+                self.rotate_up(left);
+            } else {
+                self.rotate_up(right);
+            }
+        }
+        let [left, right] = self.nodes[node].children;
+        let child = left.or(right);
+        let parent = self.nodes[node].parent;
+        if let Some(child) = child {
+            self.nodes[child].parent = parent;
+        }
+        self.replace_child(parent, node, child);
+    }
+
+    /// Makes `node` take the place of its parent.
+    fn rotate_up(&mut self, node: usize) {
+        let parent = self.nodes[node].parent.unwrap();
+        let side = usize::from(self.nodes[parent].children[1] == Some(node));
+        let inner = self.nodes[node].children[1 - side];
+        let grandparent = self.nodes[parent].parent;
+        self.replace_child(grandparent, parent, Some(node));
+        self.nodes[node].parent = grandparent;
+        self.nodes[node].children[1 - side] = Some(parent);
+        self.nodes[parent].parent = Some(node);
+        self.nodes[parent].children[side] = inner;
+        if let Some(inner) = inner {
+            self.nodes[inner].parent = Some(parent);
+        }
+    }
+
+    fn replace_child(&mut self, parent: Option<usize>, old: usize, new: Option<usize>) {
+        match parent {
+            Some(parent) => {
+                let side = usize::from(self.nodes[parent].children[1] == Some(old));
+                self.nodes[parent].children[side] = new;
+            }
+            None => self.root = new,
+        }
+    }
+
+    fn prev(&self, node: usize) -> Option<usize> {
+        self.step(node, 0)
+    }
+
+    fn next(&self, node: usize) -> Option<usize> {
+        self.step(node, 1)
+    }
+
+    /// The neighbour of `node` on the given side, 0 for below and 1 for above.
+    fn step(&self, node: usize, side: usize) -> Option<usize> {
+        if let Some(mut child) = self.nodes[node].children[side] {
+            while let Some(next) = self.nodes[child].children[1 - side] {
+                child = next;
+            }
+            return Some(child);
+        }
+        let mut child = node;
+        while let Some(parent) = self.nodes[child].parent {
+            if self.nodes[parent].children[1 - side] == Some(child) {
+                return Some(parent);
+            }
+            child = parent;
+        }
+        None
+    }
+
+    fn last(&self) -> Option<usize> {
+        let mut node = self.root?;
+        while let Some(child) = self.nodes[node].children[1] {
+            node = child;
+        }
+        Some(node)
+    }
+
+    /// The lowest node whose segment isn't `below`, given that the ones that are come
+    /// first.
+    fn lower_bound(&self, below: impl Fn(usize) -> bool) -> Option<usize> {
+        let mut found = None;
+        let mut current = self.root;
+        while let Some(node) = current {
+            if below(self.nodes[node].segment) {
+                current = self.nodes[node].children[1];
+            } else {
+                found = Some(node);
+                current = self.nodes[node].children[0];
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_rational::Rational64;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    fn segment<T: Scalar>(a: [T; 2], b: [T; 2]) -> LineSegment<T> {
+        LineSegment {
+            vertices: [Point { x: a[0], y: a[1] }, Point { x: b[0], y: b[1] }],
+        }
+    }
+
+    /// Whether the closed segments have a point in common.
+    fn meet<T: Scalar>(s: &LineSegment<T>, t: &LineSegment<T>) -> bool {
+        let [a, b] = s.vertices;
+        let [c, d] = t.vertices;
+        let orient =
+            |p: Point<T>, q: Point<T>, r: Point<T>| T::orient2d(p.into(), q.into(), r.into());
+        let within = |p: Point<T>, q: Point<T>, r: Point<T>| {
+            let [p, q] = if p.lexicographic(&q) == Ordering::Greater {
+                [q, p]
+            } else {
+                [p, q]
+            };
+            p.lexicographic(&r) != Ordering::Greater && r.lexicographic(&q) != Ordering::Greater
+        };
+        let [o1, o2, o3, o4] = [
+            orient(a, b, c),
+            orient(a, b, d),
+            orient(c, d, a),
+            orient(c, d, b),
+        ];
+        (o1 != o2 && o3 != o4 && o1.is_ne() && o2.is_ne() && o3.is_ne() && o4.is_ne())
+            || (o1.is_eq() && within(a, b, c))
+            || (o2.is_eq() && within(a, b, d))
+            || (o3.is_eq() && within(c, d, a))
+            || (o4.is_eq() && within(c, d, b))
+    }
+
+    /// Checks that the pairs of segments meeting at the intersections are exactly the
+    /// pairs that meet.
+    fn check<T: Scalar>(segments: &[LineSegment<T>], intersections: &[Intersection<T>]) {
+        let mut found = intersections
+            .iter()
+            .flat_map(|i| {
+                let s = &i.segments;
+                (0..s.len()).flat_map(move |j| (j + 1..s.len()).map(move |k| (s[j], s[k])))
+            })
+            .collect::<Vec<_>>();
+        found.sort_unstable();
+        found.dedup();
+        let mut expected = vec![];
+        for i in 0..segments.len() {
+            for j in i + 1..segments.len() {
+                if meet(&segments[i], &segments[j]) {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_cross() {
+        let segments = [
+            segment([0.0, 0.0], [2.0, 2.0]),
+            segment([0.0, 2.0], [2.0, 0.0]),
+        ];
+        let intersections = bentley_ottmann(&segments);
+        assert_eq!(
+            intersections,
+            vec![Intersection {
+                point: Point { x: 1.0, y: 1.0 },
+                segments: vec![0, 1],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        // Vertical and horizontal segments, which all cross.
+        let mut segments = vec![];
+        for i in 0..5 {
+            let x = i as f64;
+            segments.push(segment([x, -1.0], [x, 5.0]));
+            segments.push(segment([-1.0, x], [5.0, x]));
+        }
+        let intersections = bentley_ottmann(&segments);
+        assert_eq!(intersections.len(), 25);
+        check(&segments, &intersections);
+    }
+
+    #[test]
+    fn test_shared_endpoints() {
+        // A star of segments from the origin, some of them running into it.
+        let segments = (0..8)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::TAU / 8.0;
+                let end = [angle.cos(), angle.sin()];
+                if i % 2 == 0 {
+                    segment([0.0, 0.0], end)
+                } else {
+                    segment(end, [0.0, 0.0])
+                }
+            })
+            .collect::<Vec<_>>();
+        let intersections = bentley_ottmann(&segments);
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].segments, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let segments = [
+            segment([0.0, 0.0], [2.0, 0.0]),
+            segment([3.0, 0.0], [1.0, 0.0]),
+            segment([0.0, 1.0], [0.0, 3.0]),
+            segment([0.0, 2.0], [0.0, 4.0]),
+        ];
+        let intersections = bentley_ottmann(&segments);
+        let points = intersections
+            .iter()
+            .map(|i| [i.point.x, i.point.y])
+            .collect::<Vec<_>>();
+        assert_eq!(points, vec![[0.0, 2.0], [0.0, 3.0], [1.0, 0.0], [2.0, 0.0]]);
+        check(&segments, &intersections);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // Segments between points of a small grid, so that plenty of them touch, overlap
+        // or cross at the same point. Exact arithmetic keeps those points exact.
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let mut coordinate = || Rational64::from_integer(rng.gen_range(0..5));
+            let segments = (0..30)
+                .map(|_| segment([coordinate(), coordinate()], [coordinate(), coordinate()]))
+                .filter(|s| s.vertices[0] != s.vertices[1])
+                .collect::<Vec<_>>();
+            let intersections = bentley_ottmann(&segments);
+            check(&segments, &intersections);
+            // Every point is only reported once.
+            for pair in intersections.windows(2) {
+                assert_eq!(pair[0].point.lexicographic(&pair[1].point), Ordering::Less);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let segments = (0..200)
+            .map(|_| {
+                let mut point = || [rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)];
+                segment(point(), point())
+            })
+            .collect::<Vec<_>>();
+        let (intersections, steps) = bentley_ottmann_steps(&segments);
+        check(&segments, &intersections);
+        assert_eq!(steps.len(), 400 + intersections.len());
+        assert_eq!(steps.last().unwrap().found, intersections.len());
+    }
+}
@@ -0,0 +1,350 @@
+use std::cmp::Ordering;
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{
+    data::{
+        simplex::{Point, Triangle},
+        Cone, PolarDirection, Scalar,
+    },
+    predicates,
+};
+
+// https://en.wikipedia.org/wiki/Bowyer%E2%80%93Watson_algorithm
+pub fn bowyer_watson<T: Scalar>(points: &[Point<T>]) -> Vec<Triangle<T>> {
+    let mut points = points.to_vec();
+    points.sort_by(Point::lexicographic);
+    points.dedup();
+    // Inserting the points in sorted order keeps adding thin triangles along one side of
+    // the hull, which is slow as every insertion then has to dig through them. A
+    // shuffled order avoids that, and a fixed seed keeps the output reproducible.
+    points.shuffle(&mut StdRng::seed_from_u64(0));
+
+    if points.len() < 3 {
+        return vec![];
+    }
+
+    // Triangles refer to their vertices by index, see `Vertices`. Every triangle is
+    // kept next to a bound on its circumcircle, so the circles are only computed once.
+    let vertices = Vertices { points: &points };
+    let mut triangulation = vec![(vertices.super_triangle(), None)];
+
+    // The points are added one at a time to the triangulation.
+    for (i, point) in points.iter().enumerate() {
+        // First find all the triangles that are no longer valid due to the insertion.
+        let mut bad_triangles = vec![];
+        triangulation.retain(|(t, bound): &([usize; 3], Option<Bound>)| {
+            let bad = match bound {
+                Some(bound) if bound.excludes(*point) => false,
+                _ => vertices.circumcircle_contains(*t, *point),
+            };
+            if bad {
+                bad_triangles.push(*t);
+            }
+            !bad
+        });
+
+        // Find the boundary of the polygonal hole. Edges shared by two bad triangles are
+        // interior to the hole, and they show up once in each direction.
+        let hole_edges = bad_triangles.iter().flat_map(edges).collect::<Vec<_>>();
+        let polygon = hole_edges
+            .iter()
+            .copied()
+            .filter(|[a, b]| !hole_edges.contains(&[*b, *a]));
+
+        // Re-triangulate the polygonal hole. Each boundary edge runs counter-clockwise
+        // around the hole, so the new triangles are counter-clockwise as well.
+        for [a, b] in polygon {
+            let t = [a, b, i];
+            triangulation.push((t, vertices.triangle(t).and_then(Bound::new)));
+        }
+    }
+
+    // Done inserting points, now clean up.
+    triangulation
+        .into_iter()
+        .filter_map(|(t, _)| vertices.triangle(t))
+        .collect()
+}
+
+/// The vertices of the triangulation: the points, followed by the three vertices of
+/// the super triangle, a counter-clockwise triangle containing all of the points.
+///
+/// However far away a finite super triangle is placed, it still bends the circumcircles
+/// of the triangles that touch it, and the hull of the final triangulation ends up
+/// missing triangles. So its vertices are infinitely far away instead: vertex k lies at
+/// R^(k + 1) times (1, 0), (0, 1) and (-1, -1) respectively, for an arbitrarily large R.
+struct Vertices<'a, T: Scalar> {
+    points: &'a [Point<T>],
+}
+
+impl<T: Scalar> Vertices<'_, T> {
+    fn super_triangle(&self) -> [usize; 3] {
+        let n = self.points.len();
+        [n, n + 1, n + 2]
+    }
+
+    /// The triangle, unless it touches the super triangle.
+    fn triangle(&self, t: [usize; 3]) -> Option<Triangle<T>> {
+        let [a, b, c] = t;
+        Some(Triangle {
+            vertices: [
+                *self.points.get(a)?,
+                *self.points.get(b)?,
+                *self.points.get(c)?,
+            ],
+        })
+    }
+
+    /// Whether `p` lies inside the circumcircle of `t`, a counter-clockwise triangle of
+    /// the triangulation.
+    fn circumcircle_contains(&self, t: [usize; 3], p: Point<T>) -> bool {
+        // Rotate the triangle so that its finite vertices come first.
+        let [a, b, c] = t;
+        let (_, [a, b, c]) = [[a, b, c], [b, c, a], [c, a, b]]
+            .into_iter()
+            .map(|vertices| {
+                let count = vertices
+                    .iter()
+                    .take_while(|v| **v < self.points.len())
+                    .count();
+                (count, vertices)
+            })
+            .max_by_key(|(count, _)| *count)
+            .unwrap();
+
+        match [a, b, c].map(|v| self.points.get(v).copied()) {
+            [Some(a), Some(b), Some(c)] => {
+                let inside = predicates::incircle_sos(a.into(), b.into(), c.into(), p.into());
+                inside == Ordering::Greater
+            }
+            // A circle through a, b and a point infinitely far away is the half-plane
+            // left of a -> b. Points on the line only count when they split the edge
+            // a -> b.
+            [Some(a), Some(b), None] => match orientation(a, b, p) {
+                PolarDirection::CCW => true,
+                PolarDirection::CW => false,
+                PolarDirection::Collinear => {
+                    // Along a line the lexicographic order is the order of the points.
+                    let (min, max) = if a.lexicographic(&b) == Ordering::Less {
+                        (a, b)
+                    } else {
+                        (b, a)
+                    };
+                    min.lexicographic(&p) == Ordering::Less
+                        && p.lexicographic(&max) == Ordering::Less
+                }
+            },
+            // One of s1 and s2 is infinitely farther away than the other, so the circle
+            // through a, s1 and s2 is the half-plane bounded by the line through a and
+            // the nearer one (s), on the side of the farther one.
+            [Some(a), None, None] => {
+                let k1 = b - self.points.len();
+                let k2 = c - self.points.len();
+                // The side of p relative to a -> s is decided by the direction of s
+                // first, and by the origin if p is parallel to it.
+                let (side, ahead) = match k1.min(k2) {
+                    // (1, 0) × (p - a) and (1, 0) · (p - a)
+                    0 => (p.y.numeric_cmp(&a.y), p.x.numeric_cmp(&a.x)),
+                    // (0, 1) × (p - a) and (0, 1) · (p - a)
+                    _ => (a.x.numeric_cmp(&p.x), p.y.numeric_cmp(&a.y)),
+                };
+                let origin = [T::zero(), T::zero()];
+                match side.then_with(|| T::orient2d(a.into(), origin, p.into())) {
+                    // p lies on the line through a and s, so it is inside if it lies
+                    // between them.
+                    Ordering::Equal => ahead == Ordering::Greater,
+                    // s2 lies to the left of a -> s1.
+                    side => (side == Ordering::Greater) == (k1 < k2),
+                }
+            }
+            // The super triangle itself contains everything.
+            _ => true,
+        }
+    }
+}
+
+/// A circle enclosing the circumcircle of a triangle, along with the rounding errors
+/// made computing it. Points outside of it are certainly outside the circumcircle,
+/// which spares most of the (much slower) exact in-circle tests.
+struct Bound {
+    center: [f64; 2],
+    radius: f64,
+}
+
+impl Bound {
+    /// Thin triangles, whose circumcircles can't be computed accurately, and triangles
+    /// with coordinates that aren't exactly f64s are left without a bound.
+    fn new<T: Scalar>(t: Triangle<T>) -> Option<Self> {
+        let [a, b, c] = t.vertices;
+        let [a, b, c] = [exact(a)?, exact(b)?, exact(c)?];
+        let (bx, by) = (b[0] - a[0], b[1] - a[1]);
+        let (cx, cy) = (c[0] - a[0], c[1] - a[1]);
+        let d = 2.0 * (bx * cy - by * cx);
+        let b2 = bx * bx + by * by;
+        let c2 = cx * cx + cy * cy;
+        let ux = (cy * b2 - by * c2) / d;
+        let uy = (bx * c2 - cx * b2) / d;
+
+        // The relative error of d dominates, the rest is accounted for generously.
+        let error = 8.0 * f64::EPSILON * (1.0 + 2.0 * b2.sqrt() * c2.sqrt() / d.abs());
+        if error.is_nan() || error >= 1e-6 {
+            return None;
+        }
+        let radius = ux.hypot(uy);
+        let scale = a[0].abs().max(a[1].abs()) + radius;
+        Some(Self {
+            center: [a[0] + ux, a[1] + uy],
+            radius: radius * (1.0 + 4.0 * error) + 8.0 * f64::EPSILON * scale,
+        })
+    }
+
+    fn excludes<T: Scalar>(&self, p: Point<T>) -> bool {
+        let Some([x, y]) = exact(p) else {
+            return false;
+        };
+        let (dx, dy) = (x - self.center[0], y - self.center[1]);
+        let radius = self.radius + 4.0 * f64::EPSILON * x.abs().max(y.abs());
+        (dx * dx + dy * dy) * (1.0 - 8.0 * f64::EPSILON) > radius * radius
+    }
+}
+
+fn exact<T: Scalar>(p: Point<T>) -> Option<[f64; 2]> {
+    Some([p.x.to_exact_f64()?, p.y.to_exact_f64()?])
+}
+
+fn orientation<T: Scalar>(a: Point<T>, b: Point<T>, p: Point<T>) -> PolarDirection {
+    Cone {
+        a: p.into(),
+        b: b.into(),
+        origin: a.into(),
+    }
+    .angle_direction()
+}
+
+fn edges(t: &[usize; 3]) -> [[usize; 2]; 3] {
+    let [a, b, c] = *t;
+    [[a, b], [b, c], [c, a]]
+}
+
+#[cfg(test)]
+mod tests {
+    use num_rational::Rational64;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{algorithms::dewall, data::simplex::Simplex};
+
+    fn point(x: f32, y: f32) -> Point {
+        Point { x, y }
+    }
+
+    /// Sorts the vertices of every triangle (and then the triangles) so triangulations
+    /// can be compared regardless of the order in which they were built.
+    fn normalize(triangles: &[Triangle]) -> Vec<[(f32, f32); 3]> {
+        let mut triangles = triangles
+            .iter()
+            .map(|t| {
+                let mut vertices = t.vertices.map(|p| (p.x, p.y));
+                vertices.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                vertices
+            })
+            .collect::<Vec<_>>();
+        triangles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        triangles
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert!(bowyer_watson::<f32>(&[]).is_empty());
+        assert!(bowyer_watson(&[point(0.0, 0.0), point(1.0, 1.0)]).is_empty());
+        assert!(bowyer_watson(&[point(0.0, 0.0), point(1.0, 1.0), point(2.0, 2.0)]).is_empty());
+    }
+
+    #[test]
+    fn test_signed_zero() {
+        // -0.0 and 0.0 are the same coordinate, so the duplicates have to go.
+        let points = [
+            point(-0.0, 0.0),
+            point(-0.0, 0.5),
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(0.0, 1.0),
+            point(1.0, 1.0),
+        ];
+        let triangles = bowyer_watson(&points);
+        assert_eq!(triangles.len(), 3);
+        let area = triangles.iter().map(|t| t.volume()).sum::<f32>();
+        assert!((area - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_square_with_center() {
+        let points = [
+            point(-1.0, -1.0),
+            point(1.0, -1.0),
+            point(1.0, 1.0),
+            point(-1.0, 1.0),
+            point(0.0, 0.0),
+        ];
+        let triangles = bowyer_watson(&points);
+        assert_eq!(triangles.len(), 4);
+        for t in &triangles {
+            assert!(t.vertices.contains(&point(0.0, 0.0)));
+        }
+    }
+
+    #[test]
+    fn test_matches_dewall() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let points = (0..200)
+                .map(|_| point(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                normalize(&bowyer_watson(&points)),
+                normalize(&dewall(&points))
+            );
+        }
+    }
+
+    #[test]
+    fn test_grid() {
+        // Every square of the grid is cocircular, so only consistent tie breaking makes
+        // both algorithms agree on the diagonals.
+        let points = (0..10)
+            .flat_map(|i| (0..10).map(move |j| point(i as f32, j as f32)))
+            .collect::<Vec<_>>();
+        let triangles = bowyer_watson(&points);
+        assert_eq!(triangles.len(), 2 * 9 * 9);
+        assert_eq!(normalize(&triangles), normalize(&dewall(&points)));
+    }
+
+    #[test]
+    fn test_rational_grid() {
+        // Thirds aren't exact f64s, so none of the circumcircles get a bound here.
+        let points = (0..6)
+            .flat_map(|i| {
+                (0..6).map(move |j| Point::<Rational64> {
+                    x: Rational64::new(i, 3),
+                    y: Rational64::new(j, 3),
+                })
+            })
+            .collect::<Vec<_>>();
+        let triangles = bowyer_watson(&points);
+        assert_eq!(triangles.len(), 2 * 5 * 5);
+        let scaled = triangles
+            .iter()
+            .map(|t| Triangle {
+                vertices: t
+                    .vertices
+                    .map(|p| point(3.0 * p.x.to_f64() as f32, 3.0 * p.y.to_f64() as f32)),
+            })
+            .collect::<Vec<_>>();
+        let integers = points
+            .iter()
+            .map(|p| point(3.0 * p.x.to_f64() as f32, 3.0 * p.y.to_f64() as f32))
+            .collect::<Vec<_>>();
+        assert_eq!(normalize(&scaled), normalize(&bowyer_watson(&integers)));
+    }
+}
@@ -0,0 +1,190 @@
+use std::cmp::Ordering;
+
+#[cfg(test)]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+#[cfg(test)]
+use crate::data::pos2;
+use crate::{
+    algorithms::graham_scan,
+    data::{Cone, PolarDirection, Pos2, Scalar},
+};
+
+// https://en.wikipedia.org/wiki/Chan%27s_algorithm
+//
+// Returns the hull in the same order as `graham_scan`: counter-clockwise, starting at
+// the point with the lowest y-coordinate (and then the lowest x-coordinate). It takes
+// O(n log h) time for h hull points, which is optimal.
+pub fn chan<T: Scalar>(points: &[Pos2<T>]) -> Vec<Pos2<T>> {
+    // Guess the number of hull points m, squaring the guess until it is large enough.
+    // Once m reaches the number of points the attempt can no longer fail.
+    let mut m = 4usize;
+    loop {
+        m = m.min(points.len());
+        if let Some(hull) = attempt(points, m) {
+            return hull;
+        }
+        m = m.saturating_mul(m);
+    }
+}
+
+/// Finds the hull if it has at most `m` points, in O(n log m) time.
+fn attempt<T: Scalar>(points: &[Pos2<T>], m: usize) -> Option<Vec<Pos2<T>>> {
+    let Some(start) = points
+        .iter()
+        .min_by(|p1, p2| {
+            p1.y.numeric_cmp(&p2.y)
+                .then_with(|| p1.x.numeric_cmp(&p2.x))
+        })
+        .copied()
+    else {
+        return Some(vec![]);
+    };
+
+    // Split the points into groups of at most m points, and find the hull of each.
+    let mini_hulls = points.chunks(m.max(1)).map(graham_scan).collect::<Vec<_>>();
+
+    // Wrap the points like Jarvis march does, except that the candidates for the next
+    // hull point are the tangents to the mini hulls, each of which is found in
+    // O(log m) time.
+    let mut hull = vec![start];
+    while hull.len() <= m {
+        let current = hull[hull.len() - 1];
+        let next = mini_hulls
+            .iter()
+            .map(|mini_hull| tangent(current, mini_hull))
+            .filter(|p| *p != current)
+            .reduce(|best, p| if better(current, p, best) { p } else { best });
+        match next {
+            Some(next) if next != start => hull.push(next),
+            _ => return Some(hull),
+        }
+    }
+    None
+}
+
+/// Finds the point q of the convex, counter-clockwise `polygon` for which the whole
+/// polygon lies to the left of p -> q, where p lies outside the polygon or is one of
+/// its vertices.
+fn tangent<T: Scalar>(p: Pos2<T>, polygon: &[Pos2<T>]) -> Pos2<T> {
+    let n = polygon.len();
+    if n < 3 {
+        return polygon
+            .iter()
+            .copied()
+            .reduce(|best, q| if better(p, q, best) { q } else { best })
+            .unwrap();
+    }
+
+    // Seen from p, the vertices first turn clockwise up to the tangent, and then turn
+    // counter-clockwise (with p itself, if it is a vertex, ranking last). The tangent
+    // is found by a binary search for where the turn changes, on whichever side of the
+    // first vertex it lies.
+    let vertex = |i: usize| polygon[i % n];
+    let descends = |i: usize| better(p, vertex(i + 1), vertex(i));
+    if !descends(0) && !better(p, vertex(n - 1), vertex(0)) {
+        return vertex(0);
+    }
+    let before_tangent = |i: usize| {
+        if descends(0) {
+            descends(i) && better(p, vertex(i), vertex(0))
+        } else {
+            !better(p, vertex(i), vertex(0)) || descends(i)
+        }
+    };
+    let (mut low, mut high) = (1, n);
+    while low < high {
+        let mid = (low + high) / 2;
+        if before_tangent(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    vertex(low)
+}
+
+/// Whether `a` is a better candidate than `b` for the next hull point after `p`, that
+/// is `a` lies to the right of p -> b. Of points on the same line through `p` the
+/// farthest is better, and `p` itself is the worst candidate.
+fn better<T: Scalar>(p: Pos2<T>, a: Pos2<T>, b: Pos2<T>) -> bool {
+    if a == p || b == p {
+        return b == p && a != p;
+    }
+    let cone = Cone {
+        a: a.into(),
+        b: b.into(),
+        origin: p.into(),
+    };
+    match cone.angle_direction() {
+        PolarDirection::CW => true,
+        PolarDirection::CCW => false,
+        PolarDirection::Collinear => {
+            // Along a line the lexicographic order is the order of the points.
+            let pb = p.lexicographic(&b);
+            pb != Ordering::Equal && pb == b.lexicographic(&a)
+        }
+    }
+}
+
+#[test]
+fn test_chan() {
+    let bottom_left = pos2(-1.0, -1.0);
+    let bottom_right = pos2(1.0, -1.0);
+    let top_middle = pos2(0.0, 1.0);
+    let points = vec![
+        bottom_left,
+        bottom_right,
+        top_middle,
+        pos2(0.0, 0.0),
+        pos2(0.0, -1.0),
+        pos2(1.0, -1.0),
+    ];
+    assert_eq!(chan(&points), vec![bottom_left, bottom_right, top_middle]);
+
+    let points = vec![pos2(1.0, 1.0), pos2(-1.0, -1.0), pos2(0.0, 0.0)];
+    assert_eq!(chan(&points), vec![pos2(-1.0, -1.0), pos2(1.0, 1.0)]);
+    assert_eq!(chan(&[pos2(0.0, 0.0)]), vec![pos2(0.0, 0.0)]);
+    assert!(chan::<f32>(&[]).is_empty());
+}
+
+#[test]
+fn test_tangent() {
+    // A regular octagon, seen from points all around it.
+    let octagon = (0..8)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::FRAC_PI_4;
+            pos2(angle.cos(), angle.sin())
+        })
+        .collect::<Vec<_>>();
+    for i in 0..64 {
+        let angle = i as f64 * std::f64::consts::PI / 32.0;
+        let p = pos2(3.0 * angle.cos(), 3.0 * angle.sin());
+        let q = tangent(p, &octagon);
+        assert!(octagon.iter().all(|r| !better(p, *r, q)), "{p:?}");
+    }
+    // From one of its vertices, the tangent is the next one.
+    for i in 0..8 {
+        assert_eq!(tangent(octagon[i], &octagon), octagon[(i + 1) % 8]);
+    }
+}
+
+#[test]
+fn test_matches_graham_scan() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for n in [10, 50, 500] {
+        for _ in 0..20 {
+            let points = (0..n)
+                .map(|_| pos2(rng.gen_range(-1.0..1.0f32), rng.gen_range(-1.0..1.0)))
+                .collect::<Vec<_>>();
+            assert_eq!(chan(&points), graham_scan(&points));
+        }
+    }
+    // Plenty of collinear points.
+    for _ in 0..20 {
+        let points = (0..200)
+            .map(|_| pos2(rng.gen_range(-5..5) as f32, rng.gen_range(-5..5) as f32))
+            .collect::<Vec<_>>();
+        assert_eq!(chan(&points), graham_scan(&points));
+    }
+}
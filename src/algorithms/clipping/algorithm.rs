@@ -0,0 +1,186 @@
+use std::cmp::Ordering;
+
+use crate::data::{
+    simplex::{LineSegment, Point},
+    PolarDirection, Polygon, Scalar,
+};
+
+// https://en.wikipedia.org/wiki/Sutherland%E2%80%93Hodgman_algorithm
+//
+// Clips the polygon `subject` to the convex polygon `clip`, which can go around either
+// way, by cutting away the outside of each edge of `clip` in turn. This takes O(nm)
+// time for polygons with n and m vertices.
+//
+// Which side of an edge a vertex is on is decided exactly, and vertices on an edge are
+// kept. Only the points where edges cross are rounded. A concave subject can come out
+// as several pieces joined up along the edges of `clip`, by edges with nothing on
+// either side of them.
+pub fn sutherland_hodgman<T: Scalar>(subject: &[Point<T>], clip: &[Point<T>]) -> Vec<Point<T>> {
+    let mut clip = clip.to_vec();
+    if Polygon::new(clip.clone()).orientation() == PolarDirection::CW {
+        clip.reverse();
+    }
+    let mut clipped = subject.to_vec();
+    for (i, a) in clip.iter().enumerate() {
+        let b = clip[(i + 1) % clip.len()];
+        if clipped.is_empty() {
+            break;
+        }
+        let side = |p: Point<T>| T::orient2d((*a).into(), b.into(), p.into());
+        let input = std::mem::take(&mut clipped);
+        for (j, p) in input.iter().enumerate() {
+            let q = input[(j + 1) % input.len()];
+            let (side_p, side_q) = (side(*p), side(q));
+            if side_p != Ordering::Less {
+                clipped.push(*p);
+            }
+            if side_p != side_q && side_p != Ordering::Equal && side_q != Ordering::Equal {
+                // How far along pq its line crosses ab, from the areas of the triangles
+                // abp and abq.
+                let area = |r: Point<T>| (b.x - a.x) * (r.y - a.y) - (b.y - a.y) * (r.x - a.x);
+                let (area_p, area_q) = (area(*p), area(q));
+                let t = area_p / (area_p - area_q);
+                clipped.push(Point {
+                    x: p.x + (q.x - p.x) * t,
+                    y: p.y + (q.y - p.y) * t,
+                });
+            }
+        }
+    }
+    clipped
+}
+
+// https://en.wikipedia.org/wiki/Liang%E2%80%93Barsky_algorithm
+//
+// Clips a line segment to the box from `min` to `max`, returning the part of it inside
+// the box, if any. The segment is written as a + t(b - a) for t from 0 to 1, and each
+// side of the box narrows down the range of t. The clipped segment goes the same way
+// as the original one, and keeps its endpoints where they're inside the box.
+pub fn liang_barsky<T: Scalar>(
+    segment: &LineSegment<T>,
+    min: Point<T>,
+    max: Point<T>,
+) -> Option<LineSegment<T>> {
+    let [a, b] = segment.vertices;
+    let direction = b - a;
+    let (mut start, mut end) = (T::zero(), T::one());
+    for (p, q) in [
+        (-direction.x, a.x - min.x),
+        (direction.x, max.x - a.x),
+        (-direction.y, a.y - min.y),
+        (direction.y, max.y - a.y),
+    ] {
+        if p == T::zero() {
+            // Parallel to this side of the box, and either entirely inside or outside.
+            if q < T::zero() {
+                return None;
+            }
+            continue;
+        }
+        let t = q / p;
+        if p < T::zero() {
+            if t > start {
+                start = t;
+            }
+        } else if t < end {
+            end = t;
+        }
+    }
+    if start > end {
+        return None;
+    }
+    let at = |t: T| Point {
+        x: a.x + direction.x * t,
+        y: a.y + direction.y * t,
+    };
+    Some(LineSegment {
+        vertices: [
+            if start == T::zero() { a } else { at(start) },
+            if end == T::one() { b } else { at(end) },
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    fn square() -> Vec<Point<f64>> {
+        vec![
+            point(-1.0, -1.0),
+            point(1.0, -1.0),
+            point(1.0, 1.0),
+            point(-1.0, 1.0),
+        ]
+    }
+
+    #[test]
+    fn test_sutherland_hodgman() {
+        // A triangle sticking out of the right side of the square.
+        let triangle = [point(0.0, -0.5), point(3.0, 0.0), point(0.0, 0.5)];
+        let expected = [
+            point(0.0, -0.5),
+            point(1.0, -1.0 / 3.0),
+            point(1.0, 1.0 / 3.0),
+            point(0.0, 0.5),
+        ];
+        let close = |clipped: Vec<Point<f64>>| {
+            clipped.len() == expected.len()
+                && clipped
+                    .iter()
+                    .zip(&expected)
+                    .all(|(p, q)| (p.x - q.x).abs() < 1e-12 && (p.y - q.y).abs() < 1e-12)
+        };
+        assert!(close(sutherland_hodgman(&triangle, &square())));
+        // The same, clipping to the square going the other way around.
+        let mut clockwise = square();
+        clockwise.reverse();
+        assert!(close(sutherland_hodgman(&triangle, &clockwise)));
+    }
+
+    #[test]
+    fn test_sutherland_hodgman_inside_and_outside() {
+        let inside = [point(0.0, 0.0), point(0.5, 0.0), point(0.0, 0.5)];
+        assert_eq!(sutherland_hodgman(&inside, &square()), inside.to_vec());
+        let outside = [point(2.0, 0.0), point(3.0, 0.0), point(2.0, 1.0)];
+        assert!(sutherland_hodgman(&outside, &square()).is_empty());
+        // A square around the clipping square comes out as the clipping square.
+        let around = [
+            point(-2.0, -2.0),
+            point(2.0, -2.0),
+            point(2.0, 2.0),
+            point(-2.0, 2.0),
+        ];
+        let clipped = sutherland_hodgman(&around, &square());
+        assert_eq!(Polygon::new(clipped).signed_area(), 4.0);
+    }
+
+    #[test]
+    fn test_liang_barsky() {
+        let (min, max) = (point(-1.0, -1.0), point(1.0, 1.0));
+        let segment = |a: Point<f64>, b: Point<f64>| LineSegment { vertices: [a, b] };
+
+        // Crossing the box, and going the same way after clipping.
+        let clipped = liang_barsky(&segment(point(-3.0, 0.0), point(3.0, 1.5)), min, max);
+        assert_eq!(clipped, Some(segment(point(-1.0, 0.5), point(1.0, 1.0))));
+        // Inside the box, and kept as it is.
+        let inside = segment(point(-0.5, 0.1), point(0.3, -0.7));
+        assert_eq!(liang_barsky(&inside, min, max), Some(inside));
+        // Starting inside, ending outside.
+        let clipped = liang_barsky(&segment(point(0.0, 0.0), point(0.0, -5.0)), min, max);
+        assert_eq!(clipped, Some(segment(point(0.0, 0.0), point(0.0, -1.0))));
+        // Missing the box, parallel to a side and not.
+        assert_eq!(
+            liang_barsky(&segment(point(-3.0, 2.0), point(3.0, 2.0)), min, max),
+            None
+        );
+        assert_eq!(
+            liang_barsky(&segment(point(0.5, 3.0), point(3.0, 0.5)), min, max),
+            None
+        );
+    }
+}
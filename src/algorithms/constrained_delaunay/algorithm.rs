@@ -0,0 +1,584 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::{
+    algorithms::bowyer_watson,
+    data::{
+        simplex::{LineSegment, Point, Triangle},
+        Scalar,
+    },
+    predicates,
+};
+
+/// Why a constrained Delaunay triangulation couldn't be built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintError {
+    /// The constraints with these indices cross each other.
+    Intersecting(usize, usize),
+}
+
+impl fmt::Display for ConstraintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Intersecting(i, j) => write!(f, "constraints {i} and {j} intersect"),
+        }
+    }
+}
+
+impl std::error::Error for ConstraintError {}
+
+// https://en.wikipedia.org/wiki/Constrained_Delaunay_triangulation
+//
+// Triangulates the points along with the endpoints of the constraints, so that every
+// constraint is made up of edges of the triangulation. Apart from that the triangulation
+// is as Delaunay as it can be: the circumcircle of a triangle contains no point that can
+// be seen from inside the triangle without looking through a constraint.
+//
+// Constraints may share endpoints, and a constraint that passes through a point is split
+// up there, so constraints may also touch or overlap. Constraints that cross each other
+// are an error. When all the points, endpoints included, lie on a line, there are no
+// triangles, and no error either: constraints along a line can't cross.
+pub fn constrained_delaunay<T: Scalar>(
+    points: &[Point<T>],
+    constraints: &[LineSegment<T>],
+) -> Result<Vec<Triangle<T>>, ConstraintError> {
+    Ok(Triangulation::new(points, constraints)?.triangles())
+}
+
+/// A triangulation whose triangles refer to their vertices by index into `points`, all
+/// of them counter-clockwise. Removed triangles are left behind as `None`.
+pub(crate) struct Triangulation<T: Scalar> {
+    pub(crate) points: Vec<Point<T>>,
+    pub(crate) triangles: Vec<Option<[usize; 3]>>,
+    /// The triangle to the left of each directed edge.
+    pub(crate) edges: HashMap<(usize, usize), usize>,
+    /// The edges that make up the constraints inserted so far, with the lower vertex
+    /// first, and the index of the constraint each of them belongs to.
+    pub(crate) constraints: HashMap<(usize, usize), usize>,
+}
+
+impl<T: Scalar> Triangulation<T> {
+    /// The constrained Delaunay triangulation of the points and the constraints, as
+    /// described for [`constrained_delaunay`]. The input points come first in `points`,
+    /// sorted lexicographically and without duplicates.
+    pub(crate) fn new(
+        points: &[Point<T>],
+        constraints: &[LineSegment<T>],
+    ) -> Result<Self, ConstraintError> {
+        let mut points = points
+            .iter()
+            .copied()
+            .chain(constraints.iter().flat_map(|c| c.vertices))
+            .collect::<Vec<_>>();
+        points.sort_by(Point::lexicographic);
+        points.dedup();
+        let index = |p: &Point<T>| points.binary_search_by(|q| q.lexicographic(p)).unwrap();
+
+        // Split the constraints at the points on them.
+        let mut pieces = vec![];
+        for (i, constraint) in constraints.iter().enumerate() {
+            let [a, b] = constraint.vertices.map(|p| index(&p));
+            if a == b {
+                continue;
+            }
+            // The points on the constraint, in order from a to b.
+            let mut on_constraint = (0..points.len())
+                .filter(|&p| {
+                    p == a
+                        || p == b
+                        || (orientation(points[a], points[b], points[p]) == Ordering::Equal
+                            && points[a].lexicographic(&points[p])
+                                == points[p].lexicographic(&points[b]))
+                })
+                .collect::<Vec<_>>();
+            on_constraint.sort_by(|&p, &q| points[p].lexicographic(&points[q]));
+            if points[a].lexicographic(&points[b]) == Ordering::Greater {
+                on_constraint.reverse();
+            }
+            pieces.extend(on_constraint.windows(2).map(|edge| (edge[0], edge[1], i)));
+        }
+
+        // Start from the Delaunay triangulation, and then insert the constraints one by one.
+        let triangles = bowyer_watson(&points)
+            .into_iter()
+            .map(|t| t.vertices.map(|p| index(&p)))
+            .collect::<Vec<_>>();
+        let mut triangulation = Triangulation {
+            points,
+            triangles: vec![],
+            edges: HashMap::new(),
+            constraints: HashMap::new(),
+        };
+        for t in triangles {
+            triangulation.add(t);
+        }
+        if triangulation.triangles.is_empty() {
+            // Every point is collinear, so there is nothing to triangulate. The
+            // constraints lie on the same line, where they can only touch or overlap.
+            return Ok(triangulation);
+        }
+        for (a, b, i) in pieces {
+            triangulation.insert(a, b, i)?;
+        }
+        Ok(triangulation)
+    }
+
+    /// The triangles that haven't been removed.
+    pub(crate) fn triangles(&self) -> Vec<Triangle<T>> {
+        self.triangles
+            .iter()
+            .flatten()
+            .map(|t| Triangle {
+                vertices: t.map(|v| self.points[v]),
+            })
+            .collect()
+    }
+
+    fn add(&mut self, t: [usize; 3]) {
+        for [a, b] in edges(t) {
+            self.edges.insert((a, b), self.triangles.len());
+        }
+        self.triangles.push(Some(t));
+    }
+
+    fn remove(&mut self, i: usize) {
+        if let Some(t) = self.triangles[i].take() {
+            for [a, b] in edges(t) {
+                self.edges.remove(&(a, b));
+            }
+        }
+    }
+
+    /// The vertex opposite of the edge from a to b, in the triangle to its left.
+    fn apex(&self, a: usize, b: usize) -> usize {
+        let t = self.triangles[self.edges[&(a, b)]].unwrap();
+        t.into_iter().find(|&c| c != a && c != b).unwrap()
+    }
+
+    /// Makes the edge from a to b, which has no points on it, part of the triangulation.
+    fn insert(&mut self, a: usize, b: usize, constraint: usize) -> Result<(), ConstraintError> {
+        let key = (a.min(b), a.max(b));
+        if self.edges.contains_key(&(a, b)) || self.edges.contains_key(&(b, a)) {
+            self.constraints.entry(key).or_insert(constraint);
+            return Ok(());
+        }
+        let [pa, pb] = [a, b].map(|v| self.points[v]);
+
+        // Find the triangle around a that the constraint leaves a through. It is the
+        // one with b strictly inside its angle at a, as b can't lie on one of its edges.
+        let (first, [mut u, mut v]) = self
+            .triangles
+            .iter()
+            .enumerate()
+            .find_map(|(i, t)| {
+                let [p, q, r] = (*t)?;
+                let [u, v] = match a {
+                    _ if a == p => [q, r],
+                    _ if a == q => [r, p],
+                    _ if a == r => [p, q],
+                    _ => return None,
+                };
+                let inside = orientation(pa, self.points[u], pb) == Ordering::Greater
+                    && orientation(pa, self.points[v], pb) == Ordering::Less;
+                inside.then_some((i, [u, v]))
+            })
+            .unwrap();
+
+        // Walk along the constraint, through the edges it crosses, collecting the
+        // vertices on either side of it (in order from a to b).
+        let mut crossed = vec![first];
+        let mut left = vec![v];
+        let mut right = vec![u];
+        loop {
+            if let Some(&other) = self.constraints.get(&(u.min(v), u.max(v))) {
+                return Err(ConstraintError::Intersecting(other, constraint));
+            }
+            crossed.push(self.edges[&(v, u)]);
+            let w = self.apex(v, u);
+            if w == b {
+                break;
+            }
+            match orientation(pa, pb, self.points[w]) {
+                Ordering::Greater => {
+                    left.push(w);
+                    v = w;
+                }
+                Ordering::Less => {
+                    right.push(w);
+                    u = w;
+                }
+                Ordering::Equal => unreachable!("constraints are split at the points on them"),
+            }
+        }
+
+        // Clear out the triangles the constraint crosses, and fill the cavities on
+        // either side of it back in.
+        for t in crossed {
+            self.remove(t);
+        }
+        right.reverse();
+        self.fill(a, b, &left);
+        self.fill(b, a, &right);
+        self.constraints.insert(key, constraint);
+        Ok(())
+    }
+
+    /// Adds the point p inside the triangle `start`: every triangle whose circumcircle
+    /// contains p, and that can be reached from `start` without crossing a constraint,
+    /// is replaced by triangles connecting p to the boundary of that cavity. Returns
+    /// the index of p and the new triangles.
+    pub(crate) fn insert_point(&mut self, p: Point<T>, start: usize) -> (usize, Vec<usize>) {
+        self.insert_vertex(p, vec![start], None)
+    }
+
+    /// Splits the constraint edge from a to b at p, which has to lie on it (up to
+    /// rounding). Returns the index of p and the new triangles.
+    pub(crate) fn split(&mut self, a: usize, b: usize, p: Point<T>) -> (usize, Vec<usize>) {
+        let constraint = self.constraints.remove(&(a.min(b), a.max(b))).unwrap();
+        let start = [(a, b), (b, a)]
+            .iter()
+            .filter_map(|edge| self.edges.get(edge).copied())
+            .collect();
+        let (i, new) = self.insert_vertex(p, start, Some([a, b]));
+        self.constraints.insert((a.min(i), a.max(i)), constraint);
+        self.constraints.insert((b.min(i), b.max(i)), constraint);
+        (i, new)
+    }
+
+    /// The Bowyer-Watson step behind `insert_point` and `split`. The edge `split`, if
+    /// any, is the one p lies on, which disappears rather than being connected to p.
+    fn insert_vertex(
+        &mut self,
+        p: Point<T>,
+        start: Vec<usize>,
+        split: Option<[usize; 2]>,
+    ) -> (usize, Vec<usize>) {
+        let i = self.points.len();
+        self.points.push(p);
+
+        let mut cavity = start;
+        let mut in_cavity = cavity.iter().copied().collect::<HashSet<_>>();
+        let mut next = 0;
+        while let Some(&t) = cavity.get(next) {
+            next += 1;
+            for [u, v] in edges(self.triangles[t].unwrap()) {
+                if self.constraints.contains_key(&(u.min(v), u.max(v))) {
+                    continue;
+                }
+                let Some(&neighbour) = self.edges.get(&(v, u)) else {
+                    continue;
+                };
+                if in_cavity.contains(&neighbour) {
+                    continue;
+                }
+                let [a, b, c] = self.triangles[neighbour].unwrap().map(|v| self.points[v]);
+                if predicates::incircle_sos(a.into(), b.into(), c.into(), p.into())
+                    == Ordering::Greater
+                {
+                    in_cavity.insert(neighbour);
+                    cavity.push(neighbour);
+                }
+            }
+        }
+
+        let mut boundary = vec![];
+        for &t in &cavity {
+            for [u, v] in edges(self.triangles[t].unwrap()) {
+                let inside = self
+                    .edges
+                    .get(&(v, u))
+                    .is_some_and(|neighbour| in_cavity.contains(neighbour));
+                let on_p = split.is_some_and(|[a, b]| [u, v] == [a, b] || [u, v] == [b, a]);
+                if !inside && !on_p {
+                    boundary.push([u, v]);
+                }
+            }
+        }
+        for t in cavity {
+            self.remove(t);
+        }
+        let mut new = vec![];
+        for [u, v] in boundary {
+            new.push(self.triangles.len());
+            self.add([u, v, i]);
+        }
+        (i, new)
+    }
+
+    /// The triangle that contains p, found by walking towards it from `start`.
+    pub(crate) fn locate(&self, start: usize, p: Point<T>) -> Option<usize> {
+        let contains = |t: [usize; 3]| {
+            edges(t)
+                .into_iter()
+                .find(|&[u, v]| orientation(self.points[u], self.points[v], p) == Ordering::Less)
+        };
+        let mut t = start;
+        for _ in 0..self.triangles.len() {
+            match contains(self.triangles[t]?) {
+                None => return Some(t),
+                Some([u, v]) => match self.edges.get(&(v, u)) {
+                    Some(&neighbour) => t = neighbour,
+                    None => break,
+                },
+            }
+        }
+        // The walk can go around in circles when the triangulation isn't Delaunay, or
+        // leave a domain that isn't convex, so fall back to trying every triangle.
+        self.triangles
+            .iter()
+            .position(|t| t.is_some_and(|t| contains(t).is_none()))
+    }
+
+    /// Removes the triangles that can be reached from outside of the triangulation
+    /// without crossing a constraint, leaving the domain the constraints enclose.
+    pub(crate) fn remove_exterior(&mut self) {
+        let mut outside = self
+            .edges
+            .iter()
+            .filter(|&(&(a, b), _)| {
+                !self.edges.contains_key(&(b, a))
+                    && !self.constraints.contains_key(&(a.min(b), a.max(b)))
+            })
+            .map(|(_, &t)| t)
+            .collect::<Vec<_>>();
+        while let Some(t) = outside.pop() {
+            let Some(vertices) = self.triangles[t] else {
+                continue;
+            };
+            self.remove(t);
+            for [u, v] in edges(vertices) {
+                if !self.constraints.contains_key(&(u.min(v), u.max(v))) {
+                    outside.extend(self.edges.get(&(v, u)));
+                }
+            }
+        }
+    }
+
+    /// Triangulates the polygon made up of the edge from a to b and the vertices of
+    /// `chain`, which lie to the left of it in order from a to b.
+    fn fill(&mut self, a: usize, b: usize, chain: &[usize]) {
+        let [pa, pb] = [a, b].map(|v| self.points[v]);
+        // As in `dewall`, the circles through a and b are nested on this side, and the
+        // triangle to build is the one whose circle contains none of the other vertices.
+        let Some(c) = (0..chain.len()).reduce(|best, i| {
+            let [q, p] = [chain[best], chain[i]].map(|v| self.points[v]);
+            let inside = predicates::incircle_sos(pa.into(), pb.into(), q.into(), p.into());
+            if inside == Ordering::Greater {
+                i
+            } else {
+                best
+            }
+        }) else {
+            return;
+        };
+        self.add([a, b, chain[c]]);
+        self.fill(a, chain[c], &chain[..c]);
+        self.fill(chain[c], b, &chain[c + 1..]);
+    }
+}
+
+fn orientation<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> Ordering {
+    T::orient2d(a.into(), b.into(), c.into())
+}
+
+fn edges([a, b, c]: [usize; 3]) -> [[usize; 2]; 3] {
+    [[a, b], [b, c], [c, a]]
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{algorithms::graham_scan, data::pos2};
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    fn segment(a: Point<f64>, b: Point<f64>) -> LineSegment<f64> {
+        LineSegment { vertices: [a, b] }
+    }
+
+    fn has_edge(triangles: &[Triangle<f64>], a: Point<f64>, b: Point<f64>) -> bool {
+        triangles
+            .iter()
+            .any(|t| t.vertices.contains(&a) && t.vertices.contains(&b))
+    }
+
+    /// Checks that the triangles are a constrained Delaunay triangulation: the edges
+    /// that aren't part of a constraint are all locally Delaunay, which is enough for
+    /// the whole triangulation to be.
+    fn check(points: &[Point<f64>], constraints: &[LineSegment<f64>], triangles: &[Triangle<f64>]) {
+        let mut apexes = HashMap::new();
+        for t in triangles {
+            let [a, b, c] = t.vertices;
+            assert_eq!(orientation(a, b, c), Ordering::Greater);
+            for [a, b, c] in [[a, b, c], [b, c, a], [c, a, b]] {
+                apexes.insert([a, b].map(|p| (p.x.to_bits(), p.y.to_bits())), (c, t));
+            }
+        }
+        let on_constraint = |a: Point<f64>, b: Point<f64>| {
+            constraints.iter().any(|c| {
+                let [p, q] = c.vertices;
+                [a, b].into_iter().all(|r| {
+                    orientation(p, q, r) == Ordering::Equal
+                        && p.lexicographic(&r) != q.lexicographic(&r)
+                })
+            })
+        };
+        for t in triangles {
+            let [a, b, c] = t.vertices;
+            for [a, b, c] in [[a, b, c], [b, c, a], [c, a, b]] {
+                let key = [b, a].map(|p| (p.x.to_bits(), p.y.to_bits()));
+                let Some((d, _)) = apexes.get(&key) else {
+                    continue;
+                };
+                if !on_constraint(a, b) {
+                    let inside =
+                        predicates::incircle_sos(a.into(), b.into(), c.into(), (*d).into());
+                    assert_ne!(inside, Ordering::Greater, "{a:?} -> {b:?} is not Delaunay");
+                }
+            }
+        }
+
+        // Every point is used, and the triangles fill the hull: a triangulation of n
+        // points, h of which lie on the hull, has 2n - 2 - h triangles.
+        let mut all = points.to_vec();
+        all.extend(constraints.iter().flat_map(|c| c.vertices));
+        all.sort_by(Point::lexicographic);
+        all.dedup();
+        let hull = graham_scan(&all.iter().map(|p| pos2(p.x, p.y)).collect::<Vec<_>>());
+        assert_eq!(triangles.len(), 2 * all.len() - 2 - hull.len());
+    }
+
+    #[test]
+    fn test_no_constraints() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let points = (0..100)
+            .map(|_| point(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+            .collect::<Vec<_>>();
+        let triangles = constrained_delaunay(&points, &[]).unwrap();
+        assert_eq!(triangles.len(), bowyer_watson(&points).len());
+        check(&points, &[], &triangles);
+    }
+
+    #[test]
+    fn test_forced_edge() {
+        // The Delaunay triangulation uses the short diagonal.
+        let points = [
+            point(0.0, 0.0),
+            point(5.0, -1.0),
+            point(10.0, 0.0),
+            point(5.0, 1.0),
+        ];
+        let constraint = segment(points[0], points[2]);
+        let triangles = constrained_delaunay(&points, &[constraint]).unwrap();
+        assert_eq!(triangles.len(), 2);
+        assert!(has_edge(&triangles, points[0], points[2]));
+        assert!(!has_edge(&triangles, points[1], points[3]));
+    }
+
+    #[test]
+    fn test_signed_zero() {
+        // The constraint starts at a point given as -0.0 in the points.
+        let points = [
+            point(-0.0, 0.0),
+            point(1.0, 0.0),
+            point(0.0, 1.0),
+            point(1.0, 1.0),
+        ];
+        let constraint = segment(point(0.0, 0.0), point(1.0, 1.0));
+        let triangles = constrained_delaunay(&points, &[constraint]).unwrap();
+        assert_eq!(triangles.len(), 2);
+        assert!(has_edge(&triangles, points[0], points[3]));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..5 {
+            let points = (0..300)
+                .map(|_| point(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+                .collect::<Vec<_>>();
+            // Spokes around the origin, which only meet at their common endpoint, and a
+            // ring of them further out.
+            let ends = (0..12)
+                .map(|i| {
+                    let angle = i as f64 * std::f64::consts::TAU / 12.0;
+                    point(0.9 * angle.cos(), 0.9 * angle.sin())
+                })
+                .collect::<Vec<_>>();
+            let mut constraints = ends
+                .iter()
+                .map(|&end| segment(point(0.0, 0.0), end))
+                .collect::<Vec<_>>();
+            constraints.extend((0..ends.len()).map(|i| segment(ends[i], ends[(i + 1) % 12])));
+
+            let triangles = constrained_delaunay(&points, &constraints).unwrap();
+            for c in &constraints {
+                let [a, b] = c.vertices;
+                assert!(has_edge(&triangles, a, b), "{a:?} -> {b:?} is missing");
+            }
+            check(&points, &constraints, &triangles);
+        }
+    }
+
+    #[test]
+    fn test_through_points() {
+        let points = [
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(2.0, 0.0),
+            point(4.0, 0.0),
+            point(1.5, 1.0),
+            point(1.5, -1.0),
+            point(3.0, 0.1),
+        ];
+        // The first constraint is split at (1, 0) and (2, 0), the second overlaps it
+        // and the third touches it.
+        let constraints = [
+            segment(points[3], points[0]),
+            segment(points[1], point(3.0, 0.0)),
+            segment(points[6], point(3.0, 0.0)),
+        ];
+        let triangles = constrained_delaunay(&points, &constraints).unwrap();
+        for [a, b] in [[0.0, 1.0], [1.0, 2.0], [2.0, 3.0], [3.0, 4.0]] {
+            assert!(has_edge(&triangles, point(a, 0.0), point(b, 0.0)));
+        }
+        check(&points, &constraints, &triangles);
+    }
+
+    #[test]
+    fn test_collinear() {
+        // The second constraint overlaps the first, and the third touches the second.
+        let points = [point(1.0, 1.0), point(2.0, 2.0)];
+        let constraints = [
+            segment(point(0.0, 0.0), point(2.0, 2.0)),
+            segment(point(3.0, 3.0), point(1.0, 1.0)),
+            segment(point(3.0, 3.0), point(4.0, 4.0)),
+        ];
+        assert_eq!(constrained_delaunay(&points, &constraints), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_intersecting() {
+        let points = [
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 2.0),
+            point(0.0, 2.0),
+        ];
+        let constraints = [
+            segment(points[0], points[1]),
+            segment(points[0], points[2]),
+            segment(points[1], points[3]),
+        ];
+        let error = constrained_delaunay(&points, &constraints).unwrap_err();
+        assert_eq!(error, ConstraintError::Intersecting(1, 2));
+        assert_eq!(error.to_string(), "constraints 1 and 2 intersect");
+    }
+}
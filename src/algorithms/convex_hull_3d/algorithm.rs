@@ -0,0 +1,342 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::data::{point::Point3, Mesh, Scalar};
+
+// https://en.wikipedia.org/wiki/Convex_hull_algorithms#Three_dimensions
+//
+// Randomized incremental construction with a conflict graph, as in chapter 11 of de
+// Berg et al., Computational Geometry: Algorithms and Applications. Every face keeps
+// the points that can see it, and every point the faces it can see, so adding a point
+// only has to look at the part of the hull it replaces. This takes O(n log n) expected
+// time.
+//
+// The faces are oriented counter-clockwise as seen from outside. Points on the boundary
+// of the hull are left out, and points that don't span a volume (fewer than four, or all
+// of them coplanar) give an empty mesh.
+pub fn convex_hull_3d<T: Scalar>(points: &[Point3<T>]) -> Mesh<T> {
+    let mut points = points.to_vec();
+    points.sort_by(Point3::lexicographic);
+    points.dedup();
+    // A fixed seed keeps the output reproducible.
+    points.shuffle(&mut StdRng::seed_from_u64(0));
+
+    let Some(tetrahedron) = initial_tetrahedron(&points) else {
+        return Mesh::default();
+    };
+    // Put the tetrahedron first, so the other points are added in the shuffled order.
+    let mut order = tetrahedron.to_vec();
+    order.extend((0..points.len()).filter(|i| !tetrahedron.contains(i)));
+    let points = order.into_iter().map(|i| points[i]).collect::<Vec<_>>();
+
+    let mut hull = Hull {
+        points: &points,
+        faces: vec![],
+        edges: HashMap::new(),
+        conflicts: vec![vec![]; points.len()],
+    };
+    let [a, b, c, d] = [0, 1, 2, 3];
+    for face in [[a, b, c], [a, d, b], [b, d, c], [c, d, a]] {
+        let face = hull.add_face(face);
+        let candidates = (4..points.len()).collect::<Vec<_>>();
+        hull.add_conflicts(face, &candidates);
+    }
+
+    let mut visible = vec![];
+    // When each point was last added to a list of candidates, to skip duplicates.
+    let mut seen = vec![0; points.len()];
+    let mut stamp = 0;
+    for p in 4..points.len() {
+        // The faces that `p` can see, along with those it sees edge on, which are
+        // replaced as well so no new face ends up flat. They are connected, so they can
+        // be found by walking out from the faces in conflict with `p`.
+        visible.resize(hull.faces.len(), false);
+        let mut stack = hull.conflicts[p]
+            .iter()
+            .copied()
+            .filter(|&f| hull.faces[f].alive)
+            .collect::<Vec<_>>();
+        if stack.is_empty() {
+            // Inside the hull (or on its boundary).
+            continue;
+        }
+        for &f in &stack {
+            visible[f] = true;
+        }
+        let mut region = vec![];
+        while let Some(f) = stack.pop() {
+            region.push(f);
+            for [a, b] in edges(hull.faces[f].vertices) {
+                let g = hull.edges[&(b, a)];
+                if !visible[g] && hull.orient(g, p) != Ordering::Greater {
+                    visible[g] = true;
+                    stack.push(g);
+                }
+            }
+        }
+
+        // Every edge of the region that borders a face that stays becomes the base of a
+        // new face with `p`. The points that can see that face can see one of the two
+        // faces on either side of the edge.
+        let mut horizon = vec![];
+        for &f in &region {
+            for [a, b] in edges(hull.faces[f].vertices) {
+                let g = hull.edges[&(b, a)];
+                if !visible[g] {
+                    stamp += 1;
+                    let mut candidates = vec![];
+                    for &q in hull.faces[f]
+                        .conflicts
+                        .iter()
+                        .chain(&hull.faces[g].conflicts)
+                    {
+                        if q != p && seen[q] != stamp {
+                            seen[q] = stamp;
+                            candidates.push(q);
+                        }
+                    }
+                    horizon.push(([a, b], candidates));
+                }
+            }
+        }
+
+        for &f in &region {
+            hull.remove_face(f);
+            visible[f] = false;
+        }
+        for ([a, b], candidates) in horizon {
+            let face = hull.add_face([a, b, p]);
+            hull.add_conflicts(face, &candidates);
+        }
+    }
+
+    hull.into_mesh()
+}
+
+/// The convex hull so far. Faces refer to their vertices by index into `points`.
+struct Hull<'a, T: Scalar> {
+    points: &'a [Point3<T>],
+    faces: Vec<Face>,
+    /// The face to the left of each directed edge, seen from outside.
+    edges: HashMap<(usize, usize), usize>,
+    /// The faces that each point can see. Faces that have since been removed are only
+    /// skipped, not taken out.
+    conflicts: Vec<Vec<usize>>,
+}
+
+struct Face {
+    vertices: [usize; 3],
+    alive: bool,
+    /// The points that can see this face.
+    conflicts: Vec<usize>,
+}
+
+impl<T: Scalar> Hull<'_, T> {
+    /// `Less` if `p` can see `face`, `Equal` if it lies on its plane.
+    fn orient(&self, face: usize, p: usize) -> Ordering {
+        let [a, b, c] = self.faces[face].vertices.map(|v| self.points[v].into());
+        T::orient3d(a, b, c, self.points[p].into())
+    }
+
+    fn add_face(&mut self, vertices: [usize; 3]) -> usize {
+        let face = self.faces.len();
+        for [a, b] in edges(vertices) {
+            self.edges.insert((a, b), face);
+        }
+        self.faces.push(Face {
+            vertices,
+            alive: true,
+            conflicts: vec![],
+        });
+        face
+    }
+
+    fn remove_face(&mut self, face: usize) {
+        let face = &mut self.faces[face];
+        face.alive = false;
+        face.conflicts = vec![];
+        for [a, b] in edges(face.vertices) {
+            self.edges.remove(&(a, b));
+        }
+    }
+
+    /// Links `face` to those of the `candidates` that can see it.
+    fn add_conflicts(&mut self, face: usize, candidates: &[usize]) {
+        for &p in candidates {
+            if self.orient(face, p) == Ordering::Less {
+                self.faces[face].conflicts.push(p);
+                self.conflicts[p].push(face);
+            }
+        }
+    }
+
+    fn into_mesh(self) -> Mesh<T> {
+        let mut index = vec![None; self.points.len()];
+        let mut mesh = Mesh::default();
+        for face in self.faces.iter().filter(|face| face.alive) {
+            let face = face.vertices.map(|v| {
+                *index[v].get_or_insert_with(|| {
+                    mesh.vertices.push(self.points[v]);
+                    mesh.vertices.len() - 1
+                })
+            });
+            mesh.faces.push(face);
+        }
+        mesh
+    }
+}
+
+/// The directed edges of a face.
+fn edges([a, b, c]: [usize; 3]) -> [[usize; 2]; 3] {
+    [[a, b], [b, c], [c, a]]
+}
+
+/// Four points that span a volume, ordered so that the first three appear
+/// counter-clockwise from outside.
+fn initial_tetrahedron<T: Scalar>(points: &[Point3<T>]) -> Option<[usize; 4]> {
+    let coordinates = points.iter().map(|&p| p.into()).collect::<Vec<[T; 3]>>();
+    if coordinates.len() < 4 {
+        return None;
+    }
+    let [a, b] = [coordinates[0], coordinates[1]];
+    // Collinear points are collinear in every projection onto the coordinate planes, and
+    // the other way around.
+    let k = (2..coordinates.len()).find(|&k| {
+        let c = coordinates[k];
+        [[0, 1], [1, 2], [2, 0]]
+            .into_iter()
+            .any(|[x, y]| T::orient2d([a[x], a[y]], [b[x], b[y]], [c[x], c[y]]) != Ordering::Equal)
+    })?;
+    let c = coordinates[k];
+    let l = (k + 1..coordinates.len())
+        .find(|&l| T::orient3d(a, b, c, coordinates[l]) != Ordering::Equal)?;
+    Some(match T::orient3d(a, b, c, coordinates[l]) {
+        Ordering::Greater => [0, 1, k, l],
+        _ => [0, k, 1, l],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use num_rational::Rational64;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::data::point::point3;
+
+    use super::*;
+
+    /// Checks that `mesh` is a closed, consistently oriented surface with every point on
+    /// or inside it.
+    fn check<T: Scalar>(points: &[Point3<T>], mesh: &Mesh<T>) {
+        let edges = mesh
+            .faces
+            .iter()
+            .flat_map(|&face| edges(face))
+            .collect::<HashSet<_>>();
+        assert_eq!(edges.len(), 3 * mesh.faces.len(), "repeated edge");
+        for &[a, b] in &edges {
+            assert!(edges.contains(&[b, a]), "edge without a twin");
+        }
+        // Euler's formula, since the surface is a sphere.
+        let euler = mesh.vertices.len() + mesh.faces.len() - edges.len() / 2;
+        assert_eq!(euler, 2);
+
+        for face in 0..mesh.faces.len() {
+            let [a, b, c] = mesh.face(face).map(|p| p.into());
+            for (i, &p) in points.iter().enumerate() {
+                assert_ne!(
+                    T::orient3d(a, b, c, p.into()),
+                    Ordering::Less,
+                    "point {i} outside face {face}"
+                );
+            }
+            let others = mesh
+                .vertices
+                .iter()
+                .map(|&p| T::orient3d(a, b, c, p.into()));
+            assert!(
+                others.into_iter().any(|o| o == Ordering::Greater),
+                "flat face"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tetrahedron() {
+        let points = [
+            point3(0.0, 0.0, 0.0),
+            point3(1.0, 0.0, 0.0),
+            point3(0.0, 1.0, 0.0),
+            point3(0.0, 0.0, 1.0),
+            point3(0.1, 0.1, 0.1),
+        ];
+        let mesh = convex_hull_3d(&points);
+        check(&points, &mesh);
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.faces.len(), 4);
+        assert!(!mesh.vertices.contains(&points[4]));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert_eq!(convex_hull_3d::<f64>(&[]), Mesh::default());
+        let line = (0..10)
+            .map(|i| point3(i as f64, 2.0 * i as f64, 1.0))
+            .collect::<Vec<_>>();
+        assert_eq!(convex_hull_3d(&line), Mesh::default());
+        let plane = (0..20)
+            .map(|i| point3((i % 5) as f64, (i / 5) as f64, (i % 5 + i / 5) as f64))
+            .collect::<Vec<_>>();
+        assert_eq!(convex_hull_3d(&plane), Mesh::default());
+    }
+
+    #[test]
+    fn test_grid() {
+        // Lots of coplanar faces, and points on the boundary of the hull.
+        let points = (0..125)
+            .map(|i| point3((i % 5) as f32, (i / 5 % 5) as f32, (i / 25) as f32))
+            .collect::<Vec<_>>();
+        let mesh = convex_hull_3d(&points);
+        check(&points, &mesh);
+        // Only the corners of the cube are left, with two triangles on every side.
+        assert_eq!(mesh.vertices.len(), 8);
+        assert_eq!(mesh.faces.len(), 12);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for n in [4, 10, 100, 1000] {
+            let points = (0..n)
+                .map(|_| point3(rng.gen(), rng.gen(), rng.gen()))
+                .collect::<Vec<Point3<f64>>>();
+            check(&points, &convex_hull_3d(&points));
+        }
+        // Points on a sphere are all on the hull.
+        let points = (0..200)
+            .map(|_| {
+                let [x, y, z]: [f64; 3] = [(); 3].map(|_| rng.gen_range(-1.0..1.0));
+                let length = (x * x + y * y + z * z).sqrt();
+                point3(x / length, y / length, z / length)
+            })
+            .collect::<Vec<_>>();
+        let mesh = convex_hull_3d(&points);
+        check(&points, &mesh);
+        assert_eq!(mesh.vertices.len(), points.len());
+    }
+
+    #[test]
+    fn test_rational() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let points = (0..200)
+            .map(|_| {
+                let [x, y, z] = [(); 3].map(|_| Rational64::new(rng.gen_range(-8..8), 4));
+                point3(x, y, z)
+            })
+            .collect::<Vec<_>>();
+        check(&points, &convex_hull_3d(&points));
+    }
+}
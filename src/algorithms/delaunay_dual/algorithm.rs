@@ -0,0 +1,162 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::data::{
+    simplex::{Point, Simplex, Triangle},
+    Scalar, Voronoi, VoronoiEdge,
+};
+
+// https://en.wikipedia.org/wiki/Voronoi_diagram#Properties
+//
+// Builds the Voronoi diagram of `sites` from their Delaunay triangulation: the
+// circumcenters of the triangles are the vertices of the diagram, and every edge of the
+// triangulation crosses an edge of it. Edges on the hull of the triangulation cross
+// rays.
+//
+// As with `fortune`, sites which are duplicates of an earlier site are left without a
+// cell. So are all sites when they are collinear, as they have no triangulation.
+pub fn delaunay_dual<T: Scalar>(sites: &[Point<T>], triangles: &[Triangle<T>]) -> Voronoi<T> {
+    // Look the vertices of the triangles up among the sites, preferring earlier sites
+    // over their duplicates.
+    let mut order = (0..sites.len()).collect::<Vec<_>>();
+    order.sort_by(|i, j| sites[*i].lexicographic(&sites[*j]));
+    let index = |p: &Point<T>| {
+        let i = order.partition_point(|i| sites[*i].lexicographic(p) == Ordering::Less);
+        order
+            .get(i)
+            .copied()
+            .filter(|i| sites[*i] == *p)
+            .expect("the triangles are made of the sites")
+    };
+
+    // Every triangle becomes a vertex, unless it is too thin for its circumcircle to be
+    // computed, in which case its edges are treated as running off to infinity.
+    let mut vertices = vec![];
+    let mut circumcenters = vec![];
+    let mut directed_edges = HashMap::new();
+    for (t, triangle) in triangles.iter().enumerate() {
+        let center = triangle.circumscribe().map(|circle| {
+            vertices.push(Point {
+                x: circle.center.x,
+                y: circle.center.y,
+            });
+            vertices.len() - 1
+        });
+        circumcenters.push(center);
+
+        // Orient the triangle counter-clockwise, so it lies to the left of its edges.
+        let [a, b, c] = triangle.vertices;
+        let [a, b, c] = match T::orient2d(a.into(), b.into(), c.into()) {
+            Ordering::Less => [a, c, b],
+            _ => [a, b, c],
+        }
+        .map(|p| index(&p));
+        for edge in [[a, b], [b, c], [c, a]] {
+            directed_edges.insert(edge, t);
+        }
+    }
+
+    // The Voronoi edge separating a from b runs from the circumcenter of the triangle
+    // to the right of a -> b to that of the one to its left, so that a is to its left.
+    let mut edges = directed_edges
+        .iter()
+        .filter_map(|([a, b], t)| {
+            let start = match directed_edges.get(&[*b, *a]) {
+                // Only once for every pair of triangles.
+                Some(_) if a > b => return None,
+                Some(other) => circumcenters[*other],
+                None => None,
+            };
+            Some(VoronoiEdge {
+                sites: [*a, *b],
+                vertices: [start, circumcenters[*t]],
+            })
+        })
+        .collect::<Vec<_>>();
+    // The order of a hash map isn't reproducible.
+    edges.sort_by_key(|edge| edge.sites);
+
+    Voronoi {
+        sites: sites.to_vec(),
+        vertices,
+        edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::algorithms::{bowyer_watson, fortune};
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    /// The sites an edge separates, and its rounded vertices.
+    type Edge = ([usize; 2], [Option<(i64, i64)>; 2]);
+
+    /// The edges of a diagram by the sites they separate and the coordinates of their
+    /// vertices, rounded so that small differences in the circumcenters don't matter.
+    /// Edges are turned around to have the lower site to their left.
+    fn normalize(voronoi: &Voronoi<f64>) -> Vec<Edge> {
+        let mut edges = voronoi
+            .edges
+            .iter()
+            .map(|edge| {
+                let mut vertices = edge.vertices.map(|v| {
+                    v.map(|v| {
+                        let Point { x, y } = voronoi.vertices[v];
+                        ((x * 1e6).round() as i64, (y * 1e6).round() as i64)
+                    })
+                });
+                let mut sites = edge.sites;
+                if sites[0] > sites[1] {
+                    sites.reverse();
+                    vertices.reverse();
+                }
+                (sites, vertices)
+            })
+            .collect::<Vec<_>>();
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn test_triangle() {
+        let sites = [point(-1.0, 0.0), point(1.0, 0.0), point(0.0, 1.0)];
+        let triangles = [Triangle { vertices: sites }];
+        let voronoi = delaunay_dual(&sites, &triangles);
+        assert_eq!(voronoi.vertices, vec![point(0.0, 0.0)]);
+        // Three rays, each coming in from beyond the opposite hull edge.
+        assert_eq!(
+            voronoi.edges,
+            vec![
+                VoronoiEdge {
+                    sites: [0, 1],
+                    vertices: [None, Some(0)],
+                },
+                VoronoiEdge {
+                    sites: [1, 2],
+                    vertices: [None, Some(0)],
+                },
+                VoronoiEdge {
+                    sites: [2, 0],
+                    vertices: [None, Some(0)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matches_fortune() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let sites = (0..200)
+                .map(|_| point(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+                .collect::<Vec<_>>();
+            let voronoi = delaunay_dual(&sites, &bowyer_watson(&sites));
+            assert_eq!(normalize(&voronoi), normalize(&fortune(&sites)));
+        }
+    }
+}
@@ -1,11 +1,34 @@
-use std::collections::HashSet;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
 
-use crate::data::{simplex::Simplex, Vec2};
+use crate::{
+    algorithms::{convex_hull_3d, graham_scan},
+    data::{
+        point::Point3,
+        pos2,
+        simplex::{LineSegment, Point, Simplex, Tetrahedron, Triangle, Triangle3},
+        Cone, PolarDirection, Scalar,
+    },
+    predicates,
+};
 
 // DeWall: A Fast Divide & Conquer
 // Delaunay Triangulation Algorithm in Ed
 // P. Cignoniz, C. Montaniz, R. Scopigno
-pub fn dewall(points: &[Vec2]) -> Vec<Vec2> {
+pub fn dewall<T: Scalar>(points: &[Point<T>]) -> Vec<Triangle<T>> {
+    triangulate(points)
+}
+
+/// The Delaunay tetrahedralization of `points`, built the same way as the triangulation
+/// in `dewall`. Every tetrahedron has a positive volume, and points that are all
+/// coplanar give none at all.
+pub fn dewall_3d<T: Scalar>(points: &[Point3<T>]) -> Vec<Tetrahedron<T>> {
+    triangulate(points)
+}
+
+fn triangulate<T: Scalar, S: Cell<T>>(points: &[S::Vertex]) -> Vec<S> {
     // The DeWall (Delaunay Wall) algorithm consists of the following steps:
     // 1) Select the dividing plane a, split P into the two subsets P1 and P2 and construct Σa.
     // 2) Starting from Σa, recursively apply DeWall on P1 and P2 to build Σ1 and Σ2.
@@ -30,44 +53,496 @@ pub fn dewall(points: &[Vec2]) -> Vec<Vec2> {
     // with the (d-1)-faces of σ, and then again extract another face from AFL) until
     // AFL is empty.
 
-    todo!();
+    // Duplicate points would produce degenerate simplices, so we drop them up front.
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| Axis::X.cmp(a, b));
+    points.dedup();
+
+    let mut simplices = vec![];
     // Active Face List
-    let afl = vec![1];
+    let afl = vec![];
+    let hull = S::hull_faces(&points);
+    dewall_rec(points, afl, hull, Axis::X, &mut simplices);
+    simplices
+}
+
+fn dewall_rec<T: Scalar, S: Cell<T>>(
+    mut points: Vec<S::Vertex>,
+    afl: Vec<S::Face>,
+    hull: Vec<S::Face>,
+    axis: Axis,
+    sigma: &mut Vec<S>,
+) {
+    if points.len() < 2 {
+        return;
+    }
+
+    // Split P into P1 and P2 at the median along the current axis. Points are compared
+    // lexicographically, so points sharing a coordinate with the median still end up
+    // on exactly one side of α.
+    points.sort_by(|a, b| axis.cmp(a, b));
+    let wall = Wall {
+        pivot: points[points.len() / 2],
+        axis,
+    };
+
+    let mut afl_alpha = vec![];
+    let mut afl_1 = vec![];
+    let mut afl_2 = vec![];
+    for f in afl {
+        match wall.classify(f.vertices()) {
+            Side::Wall => afl_alpha.push(f),
+            Side::P1 => afl_1.push(f),
+            Side::P2 => afl_2.push(f),
+        }
+    }
+
+    // The simplices intersected by α form strips, which are built by walking along
+    // them from the faces of AFLα. A strip ends either in the AFL or on the convex
+    // hull, so the hull faces intersected by α are walked from as well (which is also
+    // how the very first simplex gets built). A hull face whose simplex is already
+    // built is still in the AFL, facing outwards, and toggling it drops both.
+    let mut hull_1 = vec![];
+    let mut hull_2 = vec![];
+    for f in hull {
+        match wall.classify(f.vertices()) {
+            Side::Wall => update::<T, S>(&mut afl_alpha, f),
+            Side::P1 => hull_1.push(f),
+            Side::P2 => hull_2.push(f),
+        }
+    }
+
+    while let Some(f) = afl_alpha.pop() {
+        let Some(simplex) = S::make_simplex(&f, &points) else {
+            // f lies on the convex hull.
+            continue;
+        };
+        for face in simplex.outward_faces() {
+            if same_face(face.vertices(), f.vertices()) {
+                continue;
+            }
+            match wall.classify(face.vertices()) {
+                Side::Wall => update::<T, S>(&mut afl_alpha, face),
+                Side::P1 => update::<T, S>(&mut afl_1, face),
+                Side::P2 => update::<T, S>(&mut afl_2, face),
+            }
+        }
+        sigma.push(simplex);
+    }
+
+    let p2 = points.split_off(points.len() / 2);
+    let p1 = points;
+    if !afl_1.is_empty() {
+        dewall_rec(p1, afl_1, hull_1, axis.next::<T, S::Vertex>(), sigma);
+    }
+    if !afl_2.is_empty() {
+        dewall_rec(p2, afl_2, hull_2, axis.next::<T, S::Vertex>(), sigma);
+    }
+}
+
+/// The simplices DeWall builds: triangles in the plane and tetrahedra in space. Their
+/// faces, which are what the AFL holds, are simplices as well: edges and triangles.
+///
+/// Faces are oriented so that the simplex still to be built lies on their positive
+/// side: to the left of an edge, or above a triangle (so that the triangle appears
+/// counter-clockwise from it).
+trait Cell<T: Scalar>:
+    Simplex<T, Vertex: Coordinates<T>, Face: Copy + Simplex<T, Vertex = Self::Vertex>> + Sized
+{
+    /// The faces of the convex hull, oriented so that the points lie on their
+    /// positive side.
+    fn hull_faces(points: &[Self::Vertex]) -> Vec<Self::Face>;
+
+    /// Given a face f, build the adjacent simplex by applying the DT definition.
+    fn make_simplex(f: &Self::Face, points: &[Self::Vertex]) -> Option<Self>;
+
+    /// The faces of the simplex, oriented so that the (not yet built) neighbouring
+    /// simplex lies on their positive side.
+    fn outward_faces(&self) -> Vec<Self::Face>;
+}
+
+impl<T: Scalar> Cell<T> for Triangle<T> {
+    fn hull_faces(points: &[Point<T>]) -> Vec<LineSegment<T>> {
+        hull_faces(points)
+    }
+
+    fn make_simplex(f: &LineSegment<T>, points: &[Point<T>]) -> Option<Self> {
+        make_simplex(f, points)
+    }
+
+    fn outward_faces(&self) -> Vec<LineSegment<T>> {
+        outward_faces(self).to_vec()
+    }
 }
 
-/// Produces a Delaunay d-simplex which is intersected by the plane α.
-fn make_first_simplex(points: &[Vec2], a: [Vec2; 2]) {
-    // Selects the point p1 ∈ P nearest to the plane. It then selects a second point p2
-    // such that p2 is the nearest point to p1 on the other side of α. Then, it searches
-    // the point p3 such that the circum-circle around the 1-face (p1, p2) and the point
-    // p3 has the minimum radius; (p1, p2, p3) is therefore a 2-face of Σ. The process
-    // continues until the required d-simplex is built.
+impl<T: Scalar> Cell<T> for Tetrahedron<T> {
+    fn hull_faces(points: &[Point3<T>]) -> Vec<Triangle3<T>> {
+        hull_faces_3d(points)
+    }
+
+    fn make_simplex(f: &Triangle3<T>, points: &[Point3<T>]) -> Option<Self> {
+        make_simplex_3d(f, points)
+    }
+
+    fn outward_faces(&self) -> Vec<Triangle3<T>> {
+        // These are counter-clockwise as seen from outside, which is where the
+        // neighbour lies.
+        self.faces()
+    }
+}
+
+/// The edges of the convex hull, oriented so that the points lie to their left.
+fn hull_faces<T: Scalar>(points: &[Point<T>]) -> Vec<LineSegment<T>> {
+    // The paper starts from a first simplex, made by picking the point p1 nearest to
+    // the wall, the point p2 nearest to p1 on the other side and then the point p3
+    // minimizing the circumcircle of (p1, p2, p3). The Euclidean nearest neighbour on
+    // the other side of α is not always a Delaunay edge though, while every edge of
+    // the convex hull is (the circle through its endpoints can grow away from the
+    // hull).
+    let hull = graham_scan(&points.iter().map(|p| pos2(p.x, p.y)).collect::<Vec<_>>());
+    if hull.len() < 3 {
+        // Every point is collinear, so there is nothing to triangulate.
+        return vec![];
+    }
+    let hull = hull.into_iter().map(Point::from).collect::<Vec<_>>();
+
+    let mut faces = vec![];
+    for i in 0..hull.len() {
+        let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+        // Points lying on a hull edge split it into shorter Delaunay edges. Along a
+        // line the lexicographic order is the order of the points.
+        let edge = LineSegment { vertices: [a, b] };
+        let mut on_edge = points
+            .iter()
+            .copied()
+            .filter(|p| halfspace(&edge, *p) == PolarDirection::Collinear)
+            .collect::<Vec<_>>();
+        on_edge.sort_by(|p, q| Axis::X.cmp(p, q));
+        if Axis::X.cmp(&a, &b) == Ordering::Greater {
+            on_edge.reverse();
+        }
+        faces.extend(on_edge.windows(2).map(|w| LineSegment {
+            vertices: [w[0], w[1]],
+        }));
+    }
+    faces
 }
 
 /// Given a face f, build the adjacent simplex by applying the DT definition.
-fn make_simplex<F: Simplex>(f: F, points: &[Vec2]) {
+///
+/// Faces are oriented so that the simplex still to be built lies to the left of them.
+fn make_simplex<T: Scalar>(f: &LineSegment<T>, points: &[Point<T>]) -> Option<Triangle<T>> {
     // For each point p ∈ P, compute the radius of the hypersphere which circumscribes p
     // and the face f. We choose the point p which, generally speaking, minimizes this
     // radius to build the simplex adjacent to f.
 
-    // selects the point p which minimizes the function dd (Delaunay distance)
+    // The circles through a and b are nested on the side of f, so minimizing the
+    // (signed) radius is the same as picking the point whose circle contains none of
+    // the others. In-circle tests find that point exactly, and break ties between
+    // cocircular points consistently.
+    let [a, b] = f.vertices;
+    points
+        .iter()
+        .copied()
+        .filter(|p| halfspace(f, *p) == PolarDirection::CCW)
+        .reduce(|best, p| {
+            let inside = predicates::incircle_sos(a.into(), b.into(), best.into(), p.into());
+            if inside == Ordering::Greater {
+                p
+            } else {
+                best
+            }
+        })
+        .map(|p| Triangle {
+            vertices: [a, b, p],
+        })
+}
+
+// Returns the halfspace (relative to the face f) which contains the point p
+fn halfspace<T: Scalar>(f: &LineSegment<T>, p: Point<T>) -> PolarDirection {
+    let [a, b] = f.vertices;
+    Cone {
+        a: p.into(),
+        b: b.into(),
+        origin: a.into(),
+    }
+    .angle_direction()
+}
+
+/// The faces of a counter-clockwise triangle, oriented so that the (not yet built)
+/// neighbouring simplex lies to their left.
+fn outward_faces<T: Scalar>(t: &Triangle<T>) -> [LineSegment<T>; 3] {
+    let [a, b, c] = t.vertices;
+    [
+        LineSegment { vertices: [b, a] },
+        LineSegment { vertices: [c, b] },
+        LineSegment { vertices: [a, c] },
+    ]
+}
+
+/// The faces of the convex hull, oriented so that the points lie above them.
+///
+/// Where several points lie on a side of the hull, that side has to be triangulated
+/// the same way as `make_simplex_3d` would, which `convex_hull_3d` knows nothing about.
+/// So the faces are gift wrapped with the same in-sphere tests, starting from an edge
+/// of the hull found by `convex_hull_3d`.
+fn hull_faces_3d<T: Scalar>(points: &[Point3<T>]) -> Vec<Triangle3<T>> {
+    let coordinates = points.iter().map(|&p| p.into()).collect::<Vec<[T; 3]>>();
+    let Some([a, b, d]) = hull_edge(points) else {
+        // Every point is coplanar, so there is nothing to tetrahedralize.
+        return vec![];
+    };
+
+    // Walk from face to face over their edges, pivoting around each edge until the
+    // face on the other side is found.
+    let mut faces = vec![];
+    let mut edges = HashSet::new();
+    let mut stack = Vec::from_iter(pivot(&coordinates, a, b, d).map(|c| [a, b, c]));
+    while let Some([a, b, c]) = stack.pop() {
+        // The same face can be found from several of its edges.
+        if !edges.insert((a, b)) {
+            continue;
+        }
+        edges.extend([(b, c), (c, a)]);
+        faces.push([a, b, c]);
+        for [u, v, w] in [[a, b, c], [b, c, a], [c, a, b]] {
+            if !edges.contains(&(v, u)) {
+                stack.extend(pivot(&coordinates, v, u, w).map(|q| [v, u, q]));
+            }
+        }
+    }
+
+    // Gift wrapping gives faces that are counter-clockwise as seen from outside.
+    faces
+        .into_iter()
+        .map(|[a, b, c]| Triangle3 {
+            vertices: [a, c, b].map(|v| points[v]),
+        })
+        .collect()
+}
+
+/// An edge `(a, b)` of the hull with no points between its ends, along with the third
+/// vertex `d` of the face `(b, a, d)` of the hull next to it (counter-clockwise as seen
+/// from outside).
+fn hull_edge<T: Scalar>(points: &[Point3<T>]) -> Option<[usize; 3]> {
+    let hull = convex_hull_3d(points);
+    let third = hull
+        .faces
+        .iter()
+        .flat_map(|&[a, b, c]| [((a, b), c), ((b, c), a), ((c, a), b)])
+        .collect::<HashMap<_, _>>();
+    // The edges between faces that aren't coplanar are edges of the hull as well,
+    // rather than diagonals of one of its sides.
+    let [a, b, d] = third.iter().find_map(|(&(a, b), &c)| {
+        let d = third[&(b, a)];
+        let [pa, pb, pc, pd] = [a, b, c, d].map(|v| hull.vertices[v].into());
+        (T::orient3d(pa, pb, pc, pd) != Ordering::Equal).then_some([a, b, d])
+    })?;
+    let [a, b, d] = [a, b, d].map(|v| {
+        let vertex = hull.vertices[v];
+        points.iter().position(|p| *p == vertex).unwrap()
+    });
+
+    // Points between the ends split the edge up.
+    let (pa, pb) = (points[a], points[b]);
+    let b = (0..points.len())
+        .filter(|&p| {
+            collinear(pa.into(), pb.into(), points[p].into())
+                && Axis::X.cmp(&pa, &points[p]) == Axis::X.cmp(&points[p], &pb)
+        })
+        .min_by(|&p, &q| {
+            let ordering = Axis::X.cmp(&points[p], &points[q]);
+            match Axis::X.cmp(&pa, &pb) {
+                Ordering::Less => ordering,
+                _ => ordering.reverse(),
+            }
+        })
+        .unwrap_or(b);
+    Some([a, b, d])
+}
+
+/// The point `q` for which `(a, b, q)` is the face of the hull next to the face
+/// `(b, a, c)`, both counter-clockwise as seen from outside.
+fn pivot<T: Scalar>(points: &[[T; 3]], a: usize, b: usize, c: usize) -> Option<usize> {
+    let [a, b, c] = [a, b, c].map(|v| points[v]);
+    // Seen from the edge, the points are spread over at most half a turn starting at
+    // the face (b, a, c). Each candidate is compared against the plane through the
+    // best one so far, which only works once the points at the very start of the
+    // turn are left out: those in the plane of (b, a, c), on the side of c.
+    let [x, y] = [[0, 1], [1, 2], [2, 0]]
+        .into_iter()
+        .find(|&[x, y]| T::orient2d([a[x], a[y]], [b[x], b[y]], [c[x], c[y]]) != Ordering::Equal)?;
+    let side = |p: [T; 3]| T::orient2d([a[x], a[y]], [b[x], b[y]], [p[x], p[y]]);
+    (0..points.len())
+        .filter(|&p| {
+            let p = points[p];
+            !collinear(a, b, p)
+                && (T::orient3d(b, a, c, p) != Ordering::Equal || side(p) != side(c))
+        })
+        .reduce(|best, i| {
+            let (q, p) = (points[best], points[i]);
+            match T::orient3d(a, b, q, p) {
+                // p lies outside the face (a, b, q).
+                Ordering::Less => i,
+                Ordering::Greater => best,
+                // p lies on the same side of the hull as q. As in `make_simplex`, the
+                // circles through a and b are nested there, and the face is the one
+                // whose circle contains none of the other points. That circle is
+                // where the spheres through the face meet this side, so testing
+                // against any of them gives the same answer as `make_simplex_3d`.
+                Ordering::Equal => {
+                    let d = if T::orient3d(a, b, q, c) != Ordering::Equal {
+                        c
+                    } else {
+                        *points
+                            .iter()
+                            .find(|&&d| T::orient3d(a, b, q, d) != Ordering::Equal)
+                            .unwrap()
+                    };
+                    if predicates::insphere_sos(a, b, q, d, p) == T::orient3d(a, b, q, d) {
+                        i
+                    } else {
+                        best
+                    }
+                }
+            }
+        })
+}
+
+/// Whether c lies on the line through a and b, which it does exactly when it does in
+/// each of the projections onto the coordinate planes.
+fn collinear<T: Scalar>(a: [T; 3], b: [T; 3], c: [T; 3]) -> bool {
+    [[0, 1], [1, 2], [2, 0]]
+        .into_iter()
+        .all(|[x, y]| T::orient2d([a[x], a[y]], [b[x], b[y]], [c[x], c[y]]) == Ordering::Equal)
 }
 
-// Returns the halfspace which contains the new tetrahedra
-fn halfspace(f: f32, p: f32) -> HashSet<()> {
-    todo!()
+/// Like `make_simplex`, with spheres in place of circles.
+fn make_simplex_3d<T: Scalar>(f: &Triangle3<T>, points: &[Point3<T>]) -> Option<Tetrahedron<T>> {
+    let [a, b, c] = f.vertices.map(<[T; 3]>::from);
+    points
+        .iter()
+        .copied()
+        .filter(|p| T::orient3d(a, b, c, (*p).into()) == Ordering::Less)
+        .reduce(|best, p| {
+            // best lies above the face, which reverses the sign of the in-sphere test.
+            let inside = predicates::insphere_sos(a, b, c, best.into(), p.into());
+            if inside == Ordering::Less {
+                p
+            } else {
+                best
+            }
+        })
+        .map(|p| Tetrahedron {
+            vertices: [f.vertices[0], f.vertices[1], f.vertices[2], p],
+        })
 }
 
-fn delaunay_distance(f: f32, p: f32) -> f32 {
-    // r and c the radius and the center of the circumsphere around f and p
-    let radius = 0.0;
-    let center = 0.0;
-    // if halfspace(f, p).contains(c) {
-    //     radius
-    // } else {
-    //     -radius
-    // }
-    radius
+fn same_face<V: PartialEq>(f1: &[V], f2: &[V]) -> bool {
+    f1.len() == f2.len() && f1.iter().all(|v| f2.contains(v))
+}
+
+/// If the face is already contained in the AFL it is removed, otherwise it is inserted.
+fn update<T: Scalar, S: Cell<T>>(afl: &mut Vec<S::Face>, f: S::Face) {
+    let vertices = f.vertices();
+    match afl.iter().position(|g| same_face(g.vertices(), vertices)) {
+        Some(i) => {
+            afl.swap_remove(i);
+        }
+        None => afl.push(f),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    /// Compares along this axis first, and then along the ones following it.
+    fn cmp<T: Scalar, V: Coordinates<T>>(self, a: &V, b: &V) -> Ordering {
+        (0..V::AXES.len())
+            .map(|i| V::AXES[(self as usize + i) % V::AXES.len()])
+            .map(|axis| a.coordinate(axis).numeric_cmp(&b.coordinate(axis)))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+
+    fn next<T: Scalar, V: Coordinates<T>>(self) -> Self {
+        V::AXES[(self as usize + 1) % V::AXES.len()]
+    }
+}
+
+/// The points DeWall works on, which are split along each of their axes in turn.
+trait Coordinates<T: Scalar>: Copy + PartialEq {
+    const AXES: &'static [Axis];
+
+    fn coordinate(&self, axis: Axis) -> T;
+}
+
+impl<T: Scalar> Coordinates<T> for Point<T> {
+    const AXES: &'static [Axis] = &[Axis::X, Axis::Y];
+
+    fn coordinate(&self, axis: Axis) -> T {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => unreachable!("points in the plane have no z-coordinate"),
+        }
+    }
+}
+
+impl<T: Scalar> Coordinates<T> for Point3<T> {
+    const AXES: &'static [Axis] = &[Axis::X, Axis::Y, Axis::Z];
+
+    fn coordinate(&self, axis: Axis) -> T {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Side {
+    P1,
+    P2,
+    Wall,
+}
+
+/// The dividing plane α. Points ordered before `pivot` along `axis` belong to P1.
+struct Wall<V> {
+    pivot: V,
+    axis: Axis,
+}
+
+impl<V> Wall<V> {
+    fn side<T: Scalar>(&self, p: &V) -> Side
+    where
+        V: Coordinates<T>,
+    {
+        match self.axis.cmp(p, &self.pivot) {
+            Ordering::Less => Side::P1,
+            _ => Side::P2,
+        }
+    }
+
+    fn classify<T: Scalar>(&self, f: &[V]) -> Side
+    where
+        V: Coordinates<T>,
+    {
+        let sides = f.iter().map(|v| self.side(v)).collect::<Vec<_>>();
+        if sides.iter().all(|side| *side == Side::P1) {
+            Side::P1
+        } else if sides.iter().all(|side| *side == Side::P2) {
+            Side::P2
+        } else {
+            Side::Wall
+        }
+    }
 }
 
 // Σ == Simplex Set?
@@ -76,3 +551,182 @@ fn delaunay_distance(f: f32, p: f32) -> f32 {
 // α
 // ∈ == element of
 // ∉ == not element of
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::data::point::point3;
+
+    fn point(x: f32, y: f32) -> Point {
+        Point { x, y }
+    }
+
+    fn assert_delaunay(points: &[Point], triangles: &[Triangle]) {
+        for t in triangles {
+            let sphere = t.circumscribe().unwrap();
+            for p in points.iter().filter(|p| !t.vertices.contains(p)) {
+                let distance = (p.x - sphere.center.x).hypot(p.y - sphere.center.y);
+                assert!(
+                    distance >= sphere.radius * (1.0 - 1e-4),
+                    "{p:?} lies inside the circumcircle of {t:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_collinear() {
+        assert!(dewall::<f32>(&[]).is_empty());
+        assert!(dewall(&[point(0.0, 0.0)]).is_empty());
+        assert!(dewall(&[point(0.0, 0.0), point(1.0, 1.0)]).is_empty());
+        assert!(dewall(&[point(0.0, 0.0), point(1.0, 1.0), point(2.0, 2.0)]).is_empty());
+    }
+
+    #[test]
+    fn test_triangle() {
+        let points = [point(0.0, 0.0), point(1.0, 0.0), point(0.0, 1.0)];
+        let triangles = dewall(&points);
+        assert_eq!(triangles.len(), 1);
+        // triangles are always counter-clockwise
+        assert!(triangles[0].volume() > 0.0);
+        let [a, b, c] = triangles[0].vertices;
+        let cone = Cone {
+            a: c.into(),
+            b: b.into(),
+            origin: a.into(),
+        };
+        assert_eq!(cone.angle_direction(), PolarDirection::CCW);
+    }
+
+    #[test]
+    fn test_square_with_center() {
+        let points = [
+            point(-1.0, -1.0),
+            point(1.0, -1.0),
+            point(1.0, 1.0),
+            point(-1.0, 1.0),
+            point(0.0, 0.0),
+        ];
+        let triangles = dewall(&points);
+        assert_eq!(triangles.len(), 4);
+        assert_delaunay(&points, &triangles);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let points = (0..200)
+                .map(|_| point(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+                .collect::<Vec<_>>();
+            let triangles = dewall(&points);
+            assert_delaunay(&points, &triangles);
+
+            // A triangulation of n points, h of which lie on the hull, has 2n - 2 - h
+            // triangles.
+            let hull = graham_scan(&points.iter().map(|p| pos2(p.x, p.y)).collect::<Vec<_>>());
+            assert_eq!(triangles.len(), 2 * points.len() - 2 - hull.len());
+        }
+    }
+
+    fn assert_delaunay_3d(points: &[Point3<f64>], tetrahedra: &[Tetrahedron<f64>]) {
+        for t in tetrahedra {
+            assert!(t.volume() > 0.0, "{t:?} is not positively oriented");
+            let sphere = t.circumscribe().unwrap();
+            for p in points.iter().filter(|p| !t.vertices.contains(p)) {
+                let [dx, dy, dz] = [
+                    p.x - sphere.center.x,
+                    p.y - sphere.center.y,
+                    p.z - sphere.center.z,
+                ];
+                let distance = (dx * dx + dy * dy + dz * dz).sqrt();
+                assert!(
+                    distance >= sphere.radius * (1.0 - 1e-9),
+                    "{p:?} lies inside the circumsphere of {t:?}"
+                );
+            }
+        }
+    }
+
+    /// Checks that the tetrahedra fill the convex hull without overlapping: each face
+    /// is shared by at most two of them, and together they have the volume of the hull.
+    fn assert_tessellation(points: &[Point3<f64>], tetrahedra: &[Tetrahedron<f64>]) {
+        let mut faces = HashMap::new();
+        for t in tetrahedra {
+            for face in t.faces() {
+                let mut face = face.vertices.map(|p| [p.x, p.y, p.z].map(f64::to_bits));
+                face.sort();
+                *faces.entry(face).or_insert(0) += 1;
+            }
+        }
+        assert!(faces.values().all(|count| *count <= 2));
+
+        let volume = tetrahedra.iter().map(|t| t.volume()).sum::<f64>();
+        let hull = convex_hull_3d(points);
+        let origin = hull.vertices[0];
+        let hull_volume = (0..hull.faces.len())
+            .map(|face| {
+                let [a, b, c] = hull.face(face);
+                // The faces are counter-clockwise from outside, so the origin sees
+                // them clockwise.
+                Tetrahedron {
+                    vertices: [a, c, b, origin],
+                }
+                .volume()
+            })
+            .sum::<f64>();
+        assert!(
+            (volume - hull_volume).abs() <= 1e-9 * hull_volume,
+            "{volume} != {hull_volume}"
+        );
+    }
+
+    #[test]
+    fn test_3d_coplanar() {
+        assert!(dewall_3d::<f64>(&[]).is_empty());
+        let points = (0..9)
+            .map(|i| point3((i % 3) as f64, (i / 3) as f64, (i % 3 + i / 3) as f64))
+            .collect::<Vec<_>>();
+        assert!(dewall_3d(&points).is_empty());
+    }
+
+    #[test]
+    fn test_3d_tetrahedron() {
+        let points = [
+            point3(0.0, 0.0, 0.0),
+            point3(1.0, 0.0, 0.0),
+            point3(0.0, 1.0, 0.0),
+            point3(0.0, 0.0, 1.0),
+        ];
+        let tetrahedra = dewall_3d(&points);
+        assert_eq!(tetrahedra.len(), 1);
+        assert!((tetrahedra[0].volume() - 1.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_3d_grid() {
+        // Every cube of the grid has eight cospherical corners, and every side of the
+        // hull nine coplanar points.
+        let points = (0..27)
+            .map(|i| point3((i % 3) as f64, (i / 3 % 3) as f64, (i / 9) as f64))
+            .collect::<Vec<_>>();
+        let tetrahedra = dewall_3d(&points);
+        assert_delaunay_3d(&points, &tetrahedra);
+        assert_tessellation(&points, &tetrahedra);
+    }
+
+    #[test]
+    fn test_3d_random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..5 {
+            let points = (0..100)
+                .map(|_| point3(rng.gen(), rng.gen(), rng.gen()))
+                .collect::<Vec<Point3<f64>>>();
+            let tetrahedra = dewall_3d(&points);
+            assert_delaunay_3d(&points, &tetrahedra);
+            assert_tessellation(&points, &tetrahedra);
+        }
+    }
+}
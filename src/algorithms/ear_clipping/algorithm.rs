@@ -0,0 +1,333 @@
+use std::cmp::Ordering;
+
+use crate::data::{
+    simplex::{Point, Triangle},
+    PolarDirection, Polygon, Scalar,
+};
+
+// https://en.wikipedia.org/wiki/Polygon_triangulation#Ear_clipping_method
+//
+// Triangulates a simple polygon by cutting off its ears one at a time: an ear is a
+// convex vertex whose triangle with its two neighbours has no other vertex in it. Every
+// simple polygon with more than three vertices has at least two of them. The triangles
+// are returned counter-clockwise, in the order their ears were clipped.
+//
+// Holes are first joined up with the exterior by a bridge: a pair of edges from a
+// vertex of the hole to a vertex of the exterior it can see, and back. That turns the
+// polygon into a single ring, which touches itself along the bridges, with n + 2h
+// vertices for n vertices and h holes, and so n + 2h - 2 triangles. Vertices where the
+// ring goes straight on are dropped rather than making flat triangles.
+//
+// Every ear test looks at all remaining vertices, so this takes O(n^3) time at worst for
+// n vertices, but close to O(n^2) in practice. A polygon that isn't simple is clipped
+// until no ears are left, which can leave parts of it uncovered.
+pub fn ear_clipping<T: Scalar>(polygon: &Polygon<T>) -> Vec<Triangle<T>> {
+    let mut ring = polygon.exterior.clone();
+    if orientation(&ring) == PolarDirection::CW {
+        ring.reverse();
+    }
+    let mut holes = polygon
+        .holes
+        .iter()
+        .filter(|hole| !hole.is_empty())
+        .map(|hole| {
+            let mut hole = hole.clone();
+            if orientation(&hole) == PolarDirection::CCW {
+                hole.reverse();
+            }
+            hole
+        })
+        .collect::<Vec<_>>();
+    // Bridging holes from right to left means each bridge can only be blocked by the
+    // exterior and the holes bridged already, which are all part of the ring by then.
+    holes.sort_by(|a, b| rightmost(b).1.x.numeric_cmp(&rightmost(a).1.x));
+    for hole in holes {
+        bridge(&mut ring, &hole);
+    }
+    clip(ring)
+}
+
+/// The index and position of the rightmost vertex (the lowest, of several).
+fn rightmost<T: Scalar>(ring: &[Point<T>]) -> (usize, Point<T>) {
+    let (i, p) = ring
+        .iter()
+        .enumerate()
+        .max_by(|(_, p), (_, q)| p.x.numeric_cmp(&q.x).then_with(|| q.y.numeric_cmp(&p.y)))
+        .unwrap();
+    (i, *p)
+}
+
+/// Splices the clockwise `hole` into the counter-clockwise `ring`, which it lies
+/// inside of, by connecting the rightmost vertex of the hole to a vertex of the ring
+/// it can see.
+fn bridge<T: Scalar>(ring: &mut Vec<Point<T>>, hole: &[Point<T>]) {
+    let (m, pm) = rightmost(hole);
+
+    // Cast a ray from the hole to the right, and find the closest edge it hits.
+    let mut hit: Option<(T, usize)> = None;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+        // Only edges going up can be hit from the inside, as the ring goes around
+        // counter-clockwise, and only those with the hole to their left.
+        if !(a.y <= pm.y && pm.y <= b.y) || a.y == b.y {
+            continue;
+        }
+        if T::orient2d(a.into(), b.into(), pm.into()) == Ordering::Less {
+            continue;
+        }
+        let x = a.x + (pm.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if hit.is_none_or(|(closest, _)| x < closest) {
+            hit = Some((x, i));
+        }
+    }
+    let Some((x, i)) = hit else {
+        // The hole isn't inside the ring after all.
+        return;
+    };
+    let hit_point = Point { x, y: pm.y };
+
+    // The end of the edge further to the right is a candidate to connect to, unless a
+    // reflex vertex of the ring lies in the triangle between the ray, the edge and that
+    // end. Then the one making the smallest angle with the ray is visible instead.
+    let (a, b) = (i, (i + 1) % ring.len());
+    let mut p = if ring[a].x > ring[b].x { a } else { b };
+    if ring[p] != hit_point {
+        let corner = ring[p];
+        // The triangle goes counter-clockwise when the corner is above the ray, and
+        // has to be turned around when it's below.
+        let triangle = if corner.y < pm.y {
+            [pm, corner, hit_point]
+        } else {
+            [pm, hit_point, corner]
+        };
+        let (mut best, mut best_p) = (None, p);
+        for j in 0..ring.len() {
+            let q = ring[j];
+            let prev = ring[(j + ring.len() - 1) % ring.len()];
+            let next = ring[(j + 1) % ring.len()];
+            let reflex = T::orient2d(prev.into(), q.into(), next.into()) != Ordering::Greater;
+            let [u, v, w] = triangle;
+            if j == p || !reflex || !in_triangle(u, v, w, q) {
+                continue;
+            }
+            // The tangent of the angle with the ray, and then the distance.
+            let (dx, dy) = (q.x - pm.x, (q.y - pm.y).abs());
+            let key = (dy / dx, dx);
+            let better = best.is_none_or(|(tangent, distance): (T, T)| {
+                key.0 < tangent || (key.0 == tangent && key.1 < distance)
+            });
+            if better {
+                best = Some(key);
+                best_p = j;
+            }
+        }
+        p = best_p;
+    }
+
+    // Go from p into the hole, around it, and back to p.
+    let mut spliced = ring[..=p].to_vec();
+    spliced.extend((0..=hole.len()).map(|k| hole[(m + k) % hole.len()]));
+    spliced.extend_from_slice(&ring[p..]);
+    *ring = spliced;
+}
+
+/// Clips the ears off the counter-clockwise `ring`.
+fn clip<T: Scalar>(ring: Vec<Point<T>>) -> Vec<Triangle<T>> {
+    let n = ring.len();
+    let mut triangles = vec![];
+    if n < 3 {
+        return triangles;
+    }
+    // The ring as a doubly linked list, so clipping a vertex is O(1).
+    let mut next = (0..n).map(|i| (i + 1) % n).collect::<Vec<_>>();
+    let mut prev = (0..n).map(|i| (i + n - 1) % n).collect::<Vec<_>>();
+    let mut remaining = n;
+
+    let mut i = 0;
+    // The number of vertices looked at since the last one was clipped.
+    let mut stuck = 0;
+    while remaining > 2 && stuck < remaining {
+        let (a, b) = (prev[i], next[i]);
+        let [pa, pi, pb] = [a, i, b].map(|v| ring[v]);
+        let clip = match T::orient2d(pa.into(), pi.into(), pb.into()) {
+            // The ring goes straight on (or doubles back on itself), so the vertex can go
+            // without a triangle.
+            Ordering::Equal => true,
+            Ordering::Less => false,
+            Ordering::Greater => {
+                // No other vertex may lie in the ear. Vertices at the same position as
+                // one of its corners are allowed, as the ring touches itself at bridges.
+                let mut v = next[b];
+                let mut empty = true;
+                while v != a {
+                    let p = ring[v];
+                    if p != pa && p != pi && p != pb && in_triangle(pa, pi, pb, p) {
+                        empty = false;
+                        break;
+                    }
+                    v = next[v];
+                }
+                if empty {
+                    triangles.push(Triangle {
+                        vertices: [pa, pi, pb],
+                    });
+                }
+                empty
+            }
+        };
+
+        if clip {
+            next[a] = b;
+            prev[b] = a;
+            remaining -= 1;
+            stuck = 0;
+            // Clipping can only turn the neighbours into ears, so look at them next.
+            i = a;
+        } else {
+            stuck += 1;
+            i = b;
+        }
+    }
+    triangles
+}
+
+/// Whether p lies inside the counter-clockwise triangle abc, or on its boundary.
+fn in_triangle<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>, p: Point<T>) -> bool {
+    [(a, b), (b, c), (c, a)]
+        .into_iter()
+        .all(|(u, v)| T::orient2d(u.into(), v.into(), p.into()) != Ordering::Less)
+}
+
+fn orientation<T: Scalar>(ring: &[Point<T>]) -> PolarDirection {
+    Polygon::new(ring.to_vec()).orientation()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::data::{
+        polygon::tests::{check_triangulation, comb, star},
+        simplex::Simplex,
+    };
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    #[test]
+    fn test_square() {
+        let square = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(0.0, 1.0),
+            point(1.0, 1.0),
+            point(1.0, 0.0),
+        ]);
+        let triangles = ear_clipping(&square);
+        check_triangulation(&square, &triangles);
+    }
+
+    #[test]
+    fn test_comb() {
+        let comb = comb();
+        assert!(comb.is_simple());
+        let triangles = ear_clipping(&comb);
+        check_triangulation(&comb, &triangles);
+    }
+
+    #[test]
+    fn test_holes() {
+        // A square with two square holes next to each other, level with each other so
+        // the bridge from the left one runs into the right one.
+        let polygon = Polygon {
+            exterior: vec![
+                point(0.0, 0.0),
+                point(10.0, 0.0),
+                point(10.0, 4.0),
+                point(0.0, 4.0),
+            ],
+            holes: vec![
+                vec![
+                    point(1.0, 1.0),
+                    point(3.0, 1.0),
+                    point(3.0, 3.0),
+                    point(1.0, 3.0),
+                ],
+                vec![
+                    point(5.0, 1.0),
+                    point(5.0, 3.0),
+                    point(7.0, 3.0),
+                    point(7.0, 1.0),
+                ],
+            ],
+        };
+        assert!(polygon.is_simple());
+        let triangles = ear_clipping(&polygon);
+        check_triangulation(&polygon, &triangles);
+    }
+
+    #[test]
+    fn test_bridge_below_ray() {
+        // The ray from the hole hits an edge whose right end is below it, and a spike
+        // of the exterior in between blocks the way to that end.
+        let polygon = Polygon {
+            exterior: vec![
+                point(-5.0, -20.0),
+                point(4.0, -20.0),
+                point(5.0, -1.0),
+                point(6.0, -20.0),
+                point(10.0, -20.0),
+                point(10.0, -10.0),
+                point(2.0, 10.0),
+                point(-5.0, 10.0),
+            ],
+            holes: vec![vec![point(-2.0, -1.0), point(-2.0, 1.0), point(0.0, 0.0)]],
+        };
+        assert!(polygon.is_simple());
+        let triangles = ear_clipping(&polygon);
+        check_triangulation(&polygon, &triangles);
+    }
+
+    #[test]
+    fn test_random() {
+        // Star-shaped polygons around the origin, with a star-shaped hole in the middle.
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let origin = point(0.0, 0.0);
+            let polygon = Polygon {
+                exterior: star(&mut rng, 50, origin, 1.0),
+                holes: vec![star(&mut rng, 20, origin, 0.4)],
+            };
+            let triangles = ear_clipping(&polygon);
+            check_triangulation(&polygon, &triangles);
+        }
+    }
+
+    #[test]
+    fn test_collinear() {
+        // A square with an extra vertex halfway along each side. Clipping can drop those
+        // instead of making triangles, but never makes a flat triangle.
+        let polygon = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 1.0),
+            point(2.0, 2.0),
+            point(1.0, 2.0),
+            point(0.0, 2.0),
+            point(0.0, 1.0),
+        ]);
+        let triangles = ear_clipping(&polygon);
+        assert!(triangles.len() <= 6);
+        for t in &triangles {
+            let [a, b, c] = t.vertices;
+            assert_eq!(
+                f64::orient2d(a.into(), b.into(), c.into()),
+                Ordering::Greater
+            );
+        }
+        let area = triangles.iter().map(|t| t.volume()).sum::<f64>();
+        assert!((area - 4.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,419 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{
+    data::{simplex::Point, Scalar, Voronoi, VoronoiEdge},
+    predicates,
+};
+
+// https://en.wikipedia.org/wiki/Fortune%27s_algorithm
+//
+// The sweep runs on the f64 approximations of the sites. Sites which share them with
+// an earlier site are duplicates, and are left without a cell.
+pub fn fortune<T: Scalar>(sites: &[Point<T>]) -> Voronoi<T> {
+    // Adding zero turns -0.0 into 0.0, so that they compare equal below.
+    let coords = sites
+        .iter()
+        .map(|p| [p.x.to_f64() + 0.0, p.y.to_f64() + 0.0])
+        .collect::<Vec<_>>();
+
+    // The sweep line moves down, meeting the sites from top to bottom (and from left to
+    // right).
+    let mut order = (0..sites.len()).collect::<Vec<_>>();
+    order.sort_by(|i, j| {
+        let ([xi, yi], [xj, yj]) = (coords[*i], coords[*j]);
+        yj.total_cmp(&yi).then_with(|| xi.total_cmp(&xj))
+    });
+    order.dedup_by(|i, j| coords[*i] == coords[*j]);
+
+    let mut sweep = Sweep {
+        sites: &coords,
+        arcs: vec![],
+        breakpoints: vec![],
+        events: BinaryHeap::new(),
+        valid: vec![],
+        next_arc: 0,
+        vertices: vec![],
+        edges: vec![],
+    };
+
+    // Circle events go first when they are level with a site, so a site landing on
+    // a vertex finds the beach line with the vanished arc already removed.
+    let mut order = order.into_iter().peekable();
+    loop {
+        while let Some(event) = sweep.events.peek() {
+            if sweep.valid[event.id] {
+                break;
+            }
+            sweep.events.pop();
+        }
+        let site = order.peek().map(|i| coords[*i]);
+        match (site, sweep.events.peek()) {
+            (Some([_, y]), Some(event)) if event.y >= y => {
+                let event = sweep.events.pop().unwrap();
+                sweep.circle_event(event);
+            }
+            (Some(_), _) => {
+                let site = order.next().unwrap();
+                sweep.site_event(site);
+            }
+            (None, Some(_)) => {
+                let event = sweep.events.pop().unwrap();
+                sweep.circle_event(event);
+            }
+            (None, None) => break,
+        }
+    }
+
+    Voronoi {
+        sites: sites.to_vec(),
+        vertices: sweep
+            .vertices
+            .into_iter()
+            .map(|[x, y]| Point {
+                x: T::from_f64(x),
+                y: T::from_f64(y),
+            })
+            .collect(),
+        edges: sweep.edges,
+    }
+}
+
+/// An arc of the beach line, the lower envelope of the parabolas of the sites above the
+/// sweep line. Arcs get an id of their own, as a site can have several of them.
+struct Arc {
+    site: usize,
+    id: usize,
+    /// The circle event at which the arc vanishes, if any.
+    event: Option<usize>,
+}
+
+/// The point at which the sweep line reaches the bottom of the circle through three
+/// sites with consecutive arcs, and the middle one vanishes.
+struct CircleEvent {
+    id: usize,
+    arc: usize,
+    center: [f64; 2],
+    y: f64,
+}
+
+impl Ord for CircleEvent {
+    // The heap pops the greatest event first, which should be the highest (and then the
+    // leftmost) one.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.y
+            .total_cmp(&other.y)
+            .then_with(|| other.center[0].total_cmp(&self.center[0]))
+    }
+}
+
+impl PartialOrd for CircleEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for CircleEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CircleEvent {}
+
+struct Sweep<'a> {
+    sites: &'a [[f64; 2]],
+    /// The arcs of the beach line, from left to right.
+    arcs: Vec<Arc>,
+    /// The edge traced out by the breakpoint between arcs i and i + 1, and the end of
+    /// it the breakpoint moves towards.
+    ///
+    /// A breakpoint moves into the part of the plane the sweep line has yet to reach,
+    /// with the site of the right arc to its left.
+    breakpoints: Vec<(usize, usize)>,
+    events: BinaryHeap<CircleEvent>,
+    /// Whether each circle event is still valid, it is not removed from the heap when
+    /// the arcs it was about change.
+    valid: Vec<bool>,
+    next_arc: usize,
+    vertices: Vec<[f64; 2]>,
+    edges: Vec<VoronoiEdge>,
+}
+
+impl Sweep<'_> {
+    fn site_event(&mut self, site: usize) {
+        let [x, l] = self.sites[site];
+        let Some(first) = self.arcs.first() else {
+            let arc = self.arc(site);
+            self.arcs.push(arc);
+            return;
+        };
+
+        // The parabolas of sites on the sweep line are vertical rays. While the sweep
+        // line hasn't left the topmost sites yet, those are side by side.
+        if self.sites[first.site][1] == l {
+            let left = self.arcs[self.arcs.len() - 1].site;
+            let edge = self.edge([site, left], [None, None]);
+            self.breakpoints.push((edge, 1));
+            let arc = self.arc(site);
+            self.arcs.push(arc);
+            return;
+        }
+
+        // Split the arc above the site in two, with the arc of the site in between.
+        let i = self.arc_above(x, l);
+        self.invalidate(i);
+        let above = self.arcs[i].site;
+        let edge = self.edge([site, above], [None, None]);
+        let arc = self.arc(site);
+        self.arcs.insert(i + 1, arc);
+        let arc = self.arc(above);
+        self.arcs.insert(i + 2, arc);
+        self.breakpoints.insert(i, (edge, 1));
+        self.breakpoints.insert(i + 1, (edge, 0));
+
+        self.check_circle(i);
+        self.check_circle(i + 2);
+    }
+
+    fn circle_event(&mut self, event: CircleEvent) {
+        // The vanishing arc is (close to) the one the sweep line finds above the center.
+        let i = self.arc_above(event.center[0], event.y);
+        let i = (0..self.arcs.len())
+            .flat_map(|d| [i.checked_add(d), i.checked_sub(d)])
+            .flatten()
+            .find(|j| self.arcs.get(*j).is_some_and(|arc| arc.id == event.arc))
+            .unwrap();
+
+        self.invalidate(i - 1);
+        self.invalidate(i + 1);
+
+        // The breakpoints around the arc meet at the center, ending their edges, and
+        // carry on as a single breakpoint starting a new one.
+        let vertex = self.vertices.len();
+        self.vertices.push(event.center);
+        for (edge, end) in [self.breakpoints[i - 1], self.breakpoints[i]] {
+            self.edges[edge].vertices[end] = Some(vertex);
+        }
+        let sites = [self.arcs[i + 1].site, self.arcs[i - 1].site];
+        let edge = self.edge(sites, [Some(vertex), None]);
+        self.arcs.remove(i);
+        self.breakpoints.remove(i);
+        self.breakpoints[i - 1] = (edge, 1);
+
+        self.check_circle(i - 1);
+        self.check_circle(i);
+    }
+
+    /// Adds a circle event for the arc at index `i`, if its breakpoints converge.
+    fn check_circle(&mut self, i: usize) {
+        if i == 0 || i + 1 >= self.arcs.len() {
+            return;
+        }
+        let [a, b, c] = [i - 1, i, i + 1].map(|j| self.sites[self.arcs[j].site]);
+        // Going down, the breakpoints around b converge when a, b and c occur in
+        // clockwise order.
+        if predicates::orient2d(a, b, c) >= 0.0 {
+            return;
+        }
+
+        // Working relative to b keeps the squared terms small.
+        let (ax, ay) = (a[0] - b[0], a[1] - b[1]);
+        let (cx, cy) = (c[0] - b[0], c[1] - b[1]);
+        let d = 2.0 * (ax * cy - ay * cx);
+        let a2 = ax * ax + ay * ay;
+        let c2 = cx * cx + cy * cy;
+        let ux = (cy * a2 - ay * c2) / d;
+        let uy = (ax * c2 - cx * a2) / d;
+
+        let id = self.valid.len();
+        self.valid.push(true);
+        self.arcs[i].event = Some(id);
+        self.events.push(CircleEvent {
+            id,
+            arc: self.arcs[i].id,
+            center: [b[0] + ux, b[1] + uy],
+            y: b[1] + uy - ux.hypot(uy),
+        });
+    }
+
+    /// Drops the circle event of the arc at index `i`, as its neighbours changed.
+    fn invalidate(&mut self, i: usize) {
+        if let Some(event) = self.arcs[i].event.take() {
+            self.valid[event] = false;
+        }
+    }
+
+    /// The index of the arc above `x`, with the sweep line at `l`.
+    fn arc_above(&self, x: f64, l: f64) -> usize {
+        let (mut low, mut high) = (0, self.arcs.len() - 1);
+        while low < high {
+            let mid = (low + high) / 2;
+            let [a, b] = [mid, mid + 1].map(|j| self.sites[self.arcs[j].site]);
+            if breakpoint(a, b, l) < x {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    fn arc(&mut self, site: usize) -> Arc {
+        self.next_arc += 1;
+        Arc {
+            site,
+            id: self.next_arc,
+            event: None,
+        }
+    }
+
+    fn edge(&mut self, sites: [usize; 2], vertices: [Option<usize>; 2]) -> usize {
+        self.edges.push(VoronoiEdge { sites, vertices });
+        self.edges.len() - 1
+    }
+}
+
+/// The x-coordinate at which the arc of `a` meets the arc of `b` to its right, with the
+/// sweep line at `l`.
+fn breakpoint(a: [f64; 2], b: [f64; 2], l: f64) -> f64 {
+    let ([ax, ay], [bx, by]) = (a, b);
+    if ay == by {
+        return (ax + bx) / 2.0;
+    }
+    if ay == l {
+        return ax;
+    }
+    if by == l {
+        return bx;
+    }
+
+    // The parabola of a is the curve y = (x - ax)^2 / da + (ay + l) / 2, and likewise
+    // for b. With u = x - ax, they meet where
+    //   (db - da) u^2 + 2 da dx u - da (dx^2 + db (by - ay) / 2) = 0
+    let (da, db, dx) = (2.0 * (ay - l), 2.0 * (by - l), bx - ax);
+    let qa = db - da;
+    let qb = 2.0 * da * dx;
+    let qc = -da * (dx * dx + db * (by - ay) / 2.0);
+    // The numerically stable form of the quadratic formula.
+    let q = -(qb + qb.signum() * (qb * qb - 4.0 * qa * qc).max(0.0).sqrt()) / 2.0;
+    let (u1, u2) = (q / qa, qc / q);
+
+    // The parabola of the site closer to the sweep line is the narrower one, and only
+    // lies below the other between the two points where they meet.
+    let u = if ay < by { u1.max(u2) } else { u1.min(u2) };
+    ax + u
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    fn distance(a: Point<f64>, b: Point<f64>) -> f64 {
+        (a.x - b.x).hypot(a.y - b.y)
+    }
+
+    /// Checks that every point of every edge is equally far from the sites of the edge,
+    /// with no site any closer, that the edges run the right way, and that the diagram
+    /// has as many edges as Euler's formula predicts.
+    fn check(voronoi: &Voronoi<f64>) {
+        for edge in &voronoi.edges {
+            let direction = voronoi.direction(edge);
+            let direction = point(direction.x, direction.y);
+            // Some points along the edge, which for rays lie far out.
+            let points = match edge.vertices.map(|v| v.map(|v| voronoi.vertices[v])) {
+                [Some(a), Some(b)] => {
+                    // Cocircular sites give zero-length edges, up to rounding errors.
+                    let along = b - a;
+                    assert!(along.x * direction.x + along.y * direction.y > -1e-9);
+                    vec![a, b]
+                }
+                [Some(a), None] => vec![a, a + point(10.0 * direction.x, 10.0 * direction.y)],
+                [None, Some(b)] => vec![b, b - point(10.0 * direction.x, 10.0 * direction.y)],
+                [None, None] => vec![],
+            };
+            for p in points {
+                let [a, b] = edge.sites.map(|i| distance(voronoi.sites[i], p));
+                assert!((a - b).abs() < 1e-9, "{p:?}");
+                let nearest = voronoi
+                    .sites
+                    .iter()
+                    .map(|site| distance(*site, p))
+                    .fold(f64::INFINITY, f64::min);
+                assert!(a - nearest < 1e-9, "{p:?}");
+            }
+        }
+        // Adding a vertex at infinity, on which all rays end, V - E + F = 2.
+        let vertices = voronoi.vertices.len() + 1;
+        assert_eq!(vertices + voronoi.sites.len(), voronoi.edges.len() + 2);
+    }
+
+    #[test]
+    fn test_two_sites() {
+        let voronoi = fortune(&[point(0.0, 1.0), point(0.0, -1.0)]);
+        assert!(voronoi.vertices.is_empty());
+        assert_eq!(
+            voronoi.edges,
+            vec![VoronoiEdge {
+                sites: [1, 0],
+                vertices: [None, None],
+            }]
+        );
+        // The edge runs with the second site to its right.
+        assert_eq!(voronoi.direction(&voronoi.edges[0]).x, -2.0);
+    }
+
+    #[test]
+    fn test_triangle() {
+        let voronoi = fortune(&[point(-1.0, 0.0), point(0.0, 1.0), point(1.0, 0.0)]);
+        assert_eq!(voronoi.vertices, vec![point(0.0, 0.0)]);
+        assert_eq!(voronoi.edges.len(), 3);
+        check(&voronoi);
+        // All three edges are rays.
+        for edge in &voronoi.edges {
+            assert!(edge.vertices.contains(&Some(0)) && edge.vertices.contains(&None));
+        }
+    }
+
+    #[test]
+    fn test_level_sites() {
+        let sites = (0..5).map(|i| point(i as f64, 0.0)).collect::<Vec<_>>();
+        let voronoi = fortune(&sites);
+        assert!(voronoi.vertices.is_empty());
+        assert_eq!(voronoi.edges.len(), 4);
+        let mut sites = sites;
+        sites.push(point(2.0, -1.0));
+        check(&fortune(&sites));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let sites = (0..200)
+                .map(|_| point(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+                .collect::<Vec<_>>();
+            check(&fortune(&sites));
+        }
+    }
+
+    #[test]
+    fn test_grid() {
+        // Every square of the grid is cocircular, so vertices coincide.
+        let sites = (0..10)
+            .flat_map(|i| (0..10).map(move |j| point(i as f64, j as f64)))
+            .collect::<Vec<_>>();
+        let voronoi = fortune(&sites);
+        check(&voronoi);
+        let mut duplicates = sites.clone();
+        duplicates.extend_from_slice(&sites[..10]);
+        let voronoi = fortune(&duplicates);
+        let cells = voronoi.cells(point(-1.0, -1.0), point(10.0, 10.0));
+        assert!(cells[100].is_empty());
+    }
+}
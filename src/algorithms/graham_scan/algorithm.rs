@@ -1,8 +1,12 @@
+use std::cmp::Ordering;
+
 use super::helpers;
-use crate::data::{pos2, Cone, Pos2};
+#[cfg(test)]
+use crate::data::pos2;
+use crate::data::{simplex::Point, Cone, PolarDirection, Polygon, Pos2, Scalar};
 
 // https://en.wikipedia.org/wiki/Graham_scan
-pub fn graham_scan(points: &[Pos2]) -> Vec<Pos2> {
+pub fn graham_scan<T: Scalar>(points: &[Pos2<T>]) -> Vec<Pos2<T>> {
     if points.is_empty() {
         return vec![];
     }
@@ -13,18 +17,21 @@ pub fn graham_scan(points: &[Pos2]) -> Vec<Pos2> {
     // chosen.
     let p0 = points
         .iter()
-        .min_by(|p1, p2| p1.y.total_cmp(&p2.y).then_with(|| p1.x.total_cmp(&p2.x)))
+        .min_by(|p1, p2| {
+            p1.y.numeric_cmp(&p2.y)
+                .then_with(|| p1.x.numeric_cmp(&p2.x))
+        })
         .unwrap();
 
     // Next, the set of points must be sorted in increasing order of the angle they and
     // the point P make with the x-axis.
     let mut points = points
-        .to_vec()
-        .into_iter()
-        .filter(|o| o != p0)
+        .iter()
+        .filter(|o| *o != p0)
+        .copied()
         .collect::<Vec<_>>();
 
-    graham_sort(p0.clone(), &mut points);
+    graham_sort(*p0, &mut points);
 
     // For each point, it is first determined whether traveling from the two points
     // immediately preceding this point constitutes making a left turn or a right turn.
@@ -39,38 +46,39 @@ pub fn graham_scan(points: &[Pos2]) -> Vec<Pos2> {
     // This process will eventually return to the point at which it started, at which
     // point the algorithm is completed and the stack now contains the points on the
     // convex hull in counterclockwise order.
-    let mut stack = vec![p0.clone()];
+    let mut stack = vec![*p0];
     for point in &points {
-        while stack.len() > 1 && helpers::to_the_right(&stack, point.clone()) {
+        while stack.len() > 1 && helpers::to_the_right(&stack, *point) {
             // pop the stack if we turn clockwise to reach this point
             stack.pop();
         }
-        stack.push(point.clone());
+        stack.push(*point);
     }
 
     stack
 }
 
-fn graham_sort(p0: Pos2, points: &mut [Pos2]) {
-    let p0 = p0;
-    points.sort_by(|a, b| {
-        let x_axis = pos2(1.0 + p0.x, p0.y);
+/// Like [`graham_scan`], but returns the hull as a counter-clockwise [`Polygon`].
+pub fn graham_scan_polygon<T: Scalar>(points: &[Pos2<T>]) -> Polygon<T> {
+    Polygon::new(graham_scan(points).into_iter().map(Point::from).collect())
+}
 
-        let a1 = Cone {
-            a: (*a).into(),
-            b: x_axis.into(),
-            origin: p0.into(),
-        };
-        let a2 = Cone {
+fn graham_sort<T: Scalar>(p0: Pos2<T>, points: &mut [Pos2<T>]) {
+    // Every point lies above p0 (or level with it, to its right), so the orientation of
+    // p0 -> a -> b tells which of a and b makes the smaller angle with the x-axis. Unlike
+    // comparing cosines it is exact, which matters for nearly collinear points. Points
+    // at the same angle are ordered by their distance to p0, which for the same reason
+    // is their order by y (and then x).
+    points.sort_by(|a, b| {
+        let cone = Cone {
             a: (*b).into(),
-            b: x_axis.into(),
+            b: (*a).into(),
             origin: p0.into(),
         };
-
-        if a1.angle() <= a2.angle() {
-            std::cmp::Ordering::Greater
-        } else {
-            std::cmp::Ordering::Less
+        match cone.angle_direction() {
+            PolarDirection::CCW => Ordering::Less,
+            PolarDirection::CW => Ordering::Greater,
+            PolarDirection::Collinear => a.y.numeric_cmp(&b.y).then_with(|| a.x.numeric_cmp(&b.x)),
         }
     });
 }
@@ -81,7 +89,7 @@ fn test_graham_sort() {
     let bottom_right = pos2(1.0, -1.0);
     let top_middle = pos2(0.0, 1.0);
     let center = pos2(0.0, 0.0);
-    let mut points = vec![center.clone(), bottom_right.clone(), top_middle.clone()];
+    let mut points = vec![center, bottom_right, top_middle];
     graham_sort(bottom_left, &mut points);
     assert_eq!(points, vec![bottom_right, center, top_middle]);
 
@@ -90,7 +98,7 @@ fn test_graham_sort() {
     let p2 = pos2(0.22698152, 0.4522189);
     let p3 = pos2(-0.034094572, 0.35310435);
     let p4 = pos2(-0.3797356, 0.35341442);
-    let mut points = vec![p4.clone(), p2.clone(), p1.clone(), p3.clone()];
+    let mut points = vec![p4, p2, p1, p3];
     graham_sort(p0, &mut points);
     assert_eq!(points, vec![p1, p2, p3, p4]);
 
@@ -99,7 +107,7 @@ fn test_graham_sort() {
     let p2 = pos2(-0.18725193, -0.058339);
     let p3 = pos2(-0.26800287, 0.27599692);
     let p4 = pos2(0.03216493, 0.38522828);
-    let mut points = vec![p3.clone(), p1.clone(), p4.clone(), p2.clone()];
+    let mut points = vec![p3, p1, p4, p2];
     graham_sort(p0, &mut points);
     assert_eq!(points, vec![p4, p3, p2, p1,]);
 }
@@ -110,9 +118,9 @@ fn test_graham_scan() {
     let bottom_right = pos2(1.0, -1.0);
     let top_middle = pos2(0.0, 1.0);
     let points = vec![
-        bottom_left.clone(),
-        bottom_right.clone(),
-        top_middle.clone(),
+        bottom_left,
+        bottom_right,
+        top_middle,
         pos2(0.0, 0.0), // centr
     ];
     assert_eq!(
@@ -125,7 +133,7 @@ fn test_graham_scan() {
     let p2 = pos2(0.22698152, 0.4522189);
     let p3 = pos2(-0.034094572, 0.35310435);
     let p4 = pos2(-0.3797356, 0.35341442);
-    let points = vec![p3.clone(), p1.clone(), p0.clone(), p4.clone(), p2.clone()];
+    let points = vec![p3, p1, p0, p4, p2];
     assert_eq!(graham_scan(&points), vec![p0, p1, p2, p4]);
 
     let p0 = pos2(0.2, -0.3);
@@ -133,6 +141,20 @@ fn test_graham_scan() {
     let p2 = pos2(-0.18725193, -0.058339);
     let p3 = pos2(-0.26800287, 0.27599692);
     let p4 = pos2(0.03216493, 0.38522828);
-    let points = vec![p3.clone(), p1.clone(), p0.clone(), p4.clone(), p2.clone()];
+    let points = vec![p3, p1, p0, p4, p2];
     assert_eq!(graham_scan(&points), vec![p0, p4, p3, p1]);
 }
+
+#[test]
+fn test_graham_scan_polygon() {
+    let points = vec![
+        pos2(-1.0, -1.0),
+        pos2(1.0, -1.0),
+        pos2(0.0, 1.0),
+        pos2(0.0, 0.0),
+    ];
+    let polygon = graham_scan_polygon(&points);
+    assert_eq!(polygon.exterior.len(), 3);
+    assert_eq!(polygon.orientation(), PolarDirection::CCW);
+    assert_eq!(polygon.signed_area(), 2.0);
+}
@@ -1,8 +1,8 @@
-use crate::data::{Cone, PolarDirection, Pos2};
+use crate::data::{Cone, PolarDirection, Pos2, Scalar};
 
-pub fn to_the_right(stack: &[Pos2], p2: Pos2) -> bool {
-    let p0 = next_to_top(&stack);
-    let p1 = top(&stack);
+pub fn to_the_right<T: Scalar>(stack: &[Pos2<T>], p2: Pos2<T>) -> bool {
+    let p0 = next_to_top(stack);
+    let p1 = top(stack);
     let cone = Cone {
         a: p0.into(),
         b: p2.into(),
@@ -21,7 +21,7 @@ fn next_to_top<T: Copy>(stack: &[T]) -> T {
 
 #[test]
 fn test_next_to_top() {
-    assert_eq!(next_to_top(&vec![1, 2, 3]), 2);
+    assert_eq!(next_to_top(&[1, 2, 3]), 2);
 }
 
 fn top<T: Copy>(stack: &[T]) -> T {
@@ -31,5 +31,5 @@ fn top<T: Copy>(stack: &[T]) -> T {
 
 #[test]
 fn test_top() {
-    assert_eq!(top(&vec![1, 2, 3]), 3);
+    assert_eq!(top(&[1, 2, 3]), 3);
 }
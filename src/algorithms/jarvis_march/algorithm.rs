@@ -0,0 +1,179 @@
+use std::cmp::Ordering;
+
+#[cfg(test)]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::data::{Cone, PolarDirection, Pos2, Scalar};
+#[cfg(test)]
+use crate::{algorithms::graham_scan, data::pos2};
+
+/// A single comparison made while wrapping, for visualizing the algorithm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JarvisStep<T: Scalar = f32> {
+    /// The number of hull points found so far. The edge out of the last of them is the
+    /// one being searched for.
+    pub hull_len: usize,
+    /// The point compared in this step.
+    pub candidate: Pos2<T>,
+    /// The end of the edge found so far, after the comparison.
+    pub best: Pos2<T>,
+}
+
+// https://en.wikipedia.org/wiki/Gift_wrapping_algorithm
+//
+// Returns the hull in the same order as `graham_scan`: counter-clockwise, starting at
+// the point with the lowest y-coordinate (and then the lowest x-coordinate). It takes
+// O(nh) time for h hull points, so it beats `graham_scan` when the hull is small.
+pub fn jarvis_march<T: Scalar>(points: &[Pos2<T>]) -> Vec<Pos2<T>> {
+    wrap(points, |_| {})
+}
+
+/// Like [`jarvis_march`], but also returns every step it took.
+pub fn jarvis_march_steps<T: Scalar>(points: &[Pos2<T>]) -> (Vec<Pos2<T>>, Vec<JarvisStep<T>>) {
+    let mut steps = vec![];
+    let hull = wrap(points, |step| steps.push(step));
+    (hull, steps)
+}
+
+fn wrap<T: Scalar>(points: &[Pos2<T>], mut on_step: impl FnMut(JarvisStep<T>)) -> Vec<Pos2<T>> {
+    // The point with the lowest y-coordinate is certainly on the hull.
+    let Some(start) = points
+        .iter()
+        .min_by(|p1, p2| {
+            p1.y.numeric_cmp(&p2.y)
+                .then_with(|| p1.x.numeric_cmp(&p2.x))
+        })
+        .copied()
+    else {
+        return vec![];
+    };
+
+    let mut hull = vec![start];
+    loop {
+        let current = hull[hull.len() - 1];
+        let Some(mut best) = points.iter().find(|p| **p != current).copied() else {
+            break;
+        };
+
+        // The next hull point is the one with all other points to the left of the edge
+        // leading to it. So whenever a point lies to the right of the edge found so far,
+        // the edge is wrapped further around to that point. Of points on the same line
+        // the farthest is taken, which leaves out the collinear ones.
+        for candidate in points.iter().copied() {
+            if candidate == current || candidate == best {
+                continue;
+            }
+            let wrap = match turn(current, best, candidate) {
+                PolarDirection::CW => true,
+                PolarDirection::CCW => false,
+                PolarDirection::Collinear => between(current, best, candidate),
+            };
+            if wrap {
+                best = candidate;
+            }
+            on_step(JarvisStep {
+                hull_len: hull.len(),
+                candidate,
+                best,
+            });
+        }
+
+        if best == start {
+            break;
+        }
+        hull.push(best);
+    }
+
+    hull
+}
+
+/// The direction of the turn made going from `a` to `b` to `c`.
+fn turn<T: Scalar>(a: Pos2<T>, b: Pos2<T>, c: Pos2<T>) -> PolarDirection {
+    Cone {
+        a: a.into(),
+        b: c.into(),
+        origin: b.into(),
+    }
+    .angle_direction()
+}
+
+/// Whether `b` lies strictly between the collinear `a` and `c`.
+fn between<T: Scalar>(a: Pos2<T>, b: Pos2<T>, c: Pos2<T>) -> bool {
+    // Along a line the lexicographic order is the order of the points.
+    let ab = a.lexicographic(&b);
+    ab != Ordering::Equal && ab == b.lexicographic(&c)
+}
+
+#[test]
+fn test_jarvis_march() {
+    let bottom_left = pos2(-1.0, -1.0);
+    let bottom_right = pos2(1.0, -1.0);
+    let top_middle = pos2(0.0, 1.0);
+    let points = vec![
+        top_middle,
+        pos2(0.0, 0.0),
+        bottom_right,
+        pos2(0.0, -1.0),
+        bottom_left,
+    ];
+    assert_eq!(
+        jarvis_march(&points),
+        vec![bottom_left, bottom_right, top_middle]
+    );
+
+    let points = vec![pos2(1.0, 1.0), pos2(-1.0, -1.0), pos2(0.0, 0.0)];
+    assert_eq!(
+        jarvis_march(&points),
+        vec![pos2(-1.0, -1.0), pos2(1.0, 1.0)]
+    );
+    assert_eq!(jarvis_march(&[pos2(0.0, 0.0); 2]), vec![pos2(0.0, 0.0)]);
+}
+
+#[test]
+fn test_jarvis_march_steps() {
+    let bottom_left = pos2(-1.0, -1.0);
+    let bottom_right = pos2(1.0, -1.0);
+    let top_middle = pos2(0.0, 1.0);
+    let center = pos2(0.0, 0.0);
+    let points = vec![center, top_middle, bottom_right, bottom_left];
+    let (hull, steps) = jarvis_march_steps(&points);
+    assert_eq!(hull, vec![bottom_left, bottom_right, top_middle]);
+    // The edge out of every hull point starts at the first other point, and is then
+    // compared against the remaining two.
+    assert_eq!(steps.len(), 3 * 2);
+    assert_eq!(
+        steps[0],
+        JarvisStep {
+            hull_len: 1,
+            candidate: top_middle,
+            best: center,
+        }
+    );
+    assert_eq!(
+        steps[1],
+        JarvisStep {
+            hull_len: 1,
+            candidate: bottom_right,
+            best: bottom_right,
+        }
+    );
+    assert_eq!(
+        steps[5],
+        JarvisStep {
+            hull_len: 3,
+            candidate: bottom_left,
+            best: bottom_left,
+        }
+    );
+}
+
+#[test]
+fn test_matches_graham_scan() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let points = (0..50)
+            .map(|_| pos2(rng.gen_range(-1.0..1.0f32), rng.gen_range(-1.0..1.0)))
+            .collect::<Vec<_>>();
+        assert_eq!(jarvis_march(&points), graham_scan(&points));
+    }
+}
@@ -0,0 +1,306 @@
+use std::cmp::Ordering;
+
+use crate::{
+    algorithms::{ear_clipping, polygon_boolean, BooleanOperation},
+    data::{simplex::Point, Cone, PolarDirection, Polygon, Scalar, Vec2},
+};
+
+// https://en.wikipedia.org/wiki/Minkowski_addition#Two_convex_polygons_in_the_plane
+//
+// The Minkowski sum of two convex polygons, the set of all sums of a point of one and
+// a point of the other, in O(n + m) time for polygons with n and m vertices. Its edges
+// are the edges of both polygons, sorted by angle, so walking around both from their
+// lowest vertices and always taking the edge that turns least gives its boundary.
+//
+// The polygons can go around either way, and can be a single point or a segment. The
+// sum goes counter-clockwise, starting from its lowest vertex, and parallel edges of
+// the two polygons make up a single edge of it.
+pub fn minkowski_sum_convex<T: Scalar>(p: &[Point<T>], q: &[Point<T>]) -> Vec<Point<T>> {
+    if p.is_empty() || q.is_empty() {
+        return vec![];
+    }
+    let [p, q] = [p, q].map(|polygon| {
+        let mut polygon = polygon.to_vec();
+        if Polygon::new(polygon.clone()).orientation() == PolarDirection::CW {
+            polygon.reverse();
+        }
+        let lowest = (0..polygon.len())
+            .min_by(|&i, &j| {
+                let (a, b) = (polygon[i], polygon[j]);
+                a.y.numeric_cmp(&b.y).then_with(|| a.x.numeric_cmp(&b.x))
+            })
+            .unwrap();
+        polygon.rotate_left(lowest);
+        polygon
+    });
+    // A single point has no edges to go along.
+    let edges = |polygon: &[Point<T>]| if polygon.len() == 1 { 0 } else { polygon.len() };
+    let (n, m) = (edges(&p), edges(&q));
+    let edge = |polygon: &[Point<T>], i: usize| {
+        Vec2::from(polygon[(i + 1) % polygon.len()] - polygon[i % polygon.len()])
+    };
+
+    let mut sum = vec![];
+    let (mut i, mut j) = (0, 0);
+    loop {
+        sum.push(p[i % p.len()] + q[j % q.len()]);
+        if i == n && j == m {
+            break;
+        }
+        // Which of the two edges turns less from the positive x-axis.
+        let turn = if i == n {
+            PolarDirection::CW
+        } else if j == m {
+            PolarDirection::CCW
+        } else {
+            Cone {
+                a: edge(&q, j),
+                b: edge(&p, i),
+                origin: Vec2::default(),
+            }
+            .angle_direction()
+        };
+        match turn {
+            PolarDirection::CCW => i += 1,
+            PolarDirection::CW => j += 1,
+            PolarDirection::Collinear => {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    // The walk ends where it started.
+    sum.pop();
+    sum
+}
+
+// The Minkowski sum of two polygons, which can have holes and don't need to be convex.
+// Both are split into convex pieces by `convex_decomposition`, and the sums of each
+// pair of pieces are combined with `polygon_boolean`. The sum can have holes where the
+// polygons don't have any, as sums of pieces close up around gaps between them.
+//
+// The sum of the pieces takes O(nm) time for polygons with n and m vertices, but
+// combining them takes a good deal longer, as they overlap a lot.
+pub fn minkowski_sum<T: Scalar>(p: &Polygon<T>, q: &Polygon<T>) -> Vec<Polygon<T>> {
+    let (p, q) = (convex_decomposition(p), convex_decomposition(q));
+    let mut sum: Vec<Polygon<T>> = vec![];
+    for a in &p {
+        for b in &q {
+            // Merge every polygon of the sum so far which the piece overlaps into it.
+            let mut merged = Polygon::new(minkowski_sum_convex(a, b));
+            let mut apart = vec![];
+            for polygon in sum {
+                let mut union = polygon_boolean(&polygon, &merged, BooleanOperation::Union);
+                if union.len() == 1 {
+                    merged = union.pop().unwrap();
+                } else {
+                    apart.push(polygon);
+                }
+            }
+            apart.push(merged);
+            sum = apart;
+        }
+    }
+    sum
+}
+
+// https://en.wikipedia.org/wiki/Polygon_partition#Partition_into_convex_polygons
+//
+// Splits a polygon, which can have holes, into convex pieces going counter-clockwise.
+// The polygon is triangulated by `ear_clipping`, after which neighbouring pieces are
+// merged for as long as that keeps them convex (Hertel-Mehlhorn). This takes O(n^3)
+// time at worst for n vertices, and gives at most four times as many pieces as needed.
+// A polygon without any area comes out as a single point or segment.
+pub fn convex_decomposition<T: Scalar>(polygon: &Polygon<T>) -> Vec<Vec<Point<T>>> {
+    let mut pieces = ear_clipping(polygon)
+        .into_iter()
+        .map(|t| t.vertices.to_vec())
+        .collect::<Vec<_>>();
+    if pieces.is_empty() && !polygon.exterior.is_empty() {
+        // A polygon without any area is a point or a segment, between its two ends.
+        let order =
+            |p: &&Point<T>, q: &&Point<T>| p.y.numeric_cmp(&q.y).then(p.x.numeric_cmp(&q.x));
+        let ends = [
+            polygon.exterior.iter().min_by(order),
+            polygon.exterior.iter().max_by(order),
+        ];
+        let mut piece = ends.map(|end| *end.unwrap()).to_vec();
+        piece.dedup();
+        return vec![piece];
+    }
+    let mut merging = true;
+    while merging {
+        merging = false;
+        'pieces: for a in 0..pieces.len() {
+            for b in a + 1..pieces.len() {
+                if let Some(merged) = merge(&pieces[a], &pieces[b]) {
+                    pieces[a] = merged;
+                    pieces.swap_remove(b);
+                    merging = true;
+                    break 'pieces;
+                }
+            }
+        }
+    }
+    pieces
+}
+
+/// The two counter-clockwise convex pieces merged along an edge they share, if they
+/// share one and the merged piece is convex.
+fn merge<T: Scalar>(a: &[Point<T>], b: &[Point<T>]) -> Option<Vec<Point<T>>> {
+    let (n, m) = (a.len(), b.len());
+    for i in 0..n {
+        for j in 0..m {
+            // The edge from a[i] to a[i + 1] runs the other way around b.
+            if a[i] != b[(j + 1) % m] || a[(i + 1) % n] != b[j] {
+                continue;
+            }
+            // Go around a to the start of the edge, then around b back to its end.
+            let mut merged = (0..n).map(|k| a[(i + 1 + k) % n]).collect::<Vec<_>>();
+            merged.extend((2..m).map(|k| b[(j + k) % m]));
+            let convex = (0..merged.len()).all(|k| {
+                let [u, v, w] = [k, k + 1, k + 2].map(|k| merged[k % merged.len()]);
+                T::orient2d(u.into(), v.into(), w.into()) != Ordering::Less
+            });
+            return convex.then_some(merged);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::polygon::tests::comb;
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    fn square(min: f64, max: f64) -> Vec<Point<f64>> {
+        vec![
+            point(min, min),
+            point(max, min),
+            point(max, max),
+            point(min, max),
+        ]
+    }
+
+    fn area(polygons: &[Polygon<f64>]) -> f64 {
+        polygons.iter().map(|polygon| polygon.signed_area()).sum()
+    }
+
+    #[test]
+    fn test_convex() {
+        // Parallel edges make up a single edge of the sum.
+        assert_eq!(
+            minkowski_sum_convex(&square(0.0, 1.0), &square(-1.0, 1.0)),
+            square(-1.0, 2.0)
+        );
+        // A triangle going clockwise, with edges parallel to two of the square's.
+        let triangle = [point(0.0, 0.0), point(0.0, 1.0), point(1.0, 0.0)];
+        assert_eq!(
+            minkowski_sum_convex(&triangle, &square(0.0, 1.0)),
+            vec![
+                point(0.0, 0.0),
+                point(2.0, 0.0),
+                point(2.0, 1.0),
+                point(1.0, 2.0),
+                point(0.0, 2.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // Adding a point moves the polygon.
+        let moved = minkowski_sum_convex(&[point(1.0, 2.0)], &square(0.0, 1.0));
+        assert_eq!(
+            moved,
+            vec![
+                point(1.0, 2.0),
+                point(2.0, 2.0),
+                point(2.0, 3.0),
+                point(1.0, 3.0)
+            ]
+        );
+        // Adding a segment sweeps the polygon along it.
+        let segment = [point(0.0, 0.0), point(2.0, 1.0)];
+        let swept = minkowski_sum_convex(&segment, &square(0.0, 1.0));
+        assert_eq!(swept.len(), 6);
+        assert_eq!(Polygon::new(swept).signed_area(), 4.0);
+    }
+
+    #[test]
+    fn test_concave() {
+        // An L made of three unit squares, grown by a unit square.
+        let l = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 1.0),
+            point(1.0, 1.0),
+            point(1.0, 2.0),
+            point(0.0, 2.0),
+        ]);
+        let sum = minkowski_sum(&l, &Polygon::new(square(0.0, 1.0)));
+        assert_eq!(sum.len(), 1);
+        assert!(sum[0].holes.is_empty());
+        assert!((area(&sum) - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_holes() {
+        // A frame grown by half a unit, which shrinks its hole.
+        let frame = Polygon {
+            exterior: square(0.0, 4.0),
+            holes: vec![square(1.0, 3.0)],
+        };
+        let sum = minkowski_sum(&frame, &Polygon::new(square(0.0, 0.5)));
+        assert_eq!(sum.len(), 1);
+        assert_eq!(sum[0].holes.len(), 1);
+        assert!((area(&sum) - (4.5 * 4.5 - 1.5 * 1.5)).abs() < 1e-9);
+
+        // A ring with a narrow mouth at the top, which the sum closes up, leaving the
+        // right of the inside as a hole.
+        let ring = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(5.0, 0.0),
+            point(5.0, 5.0),
+            point(3.0, 5.0),
+            point(3.0, 4.0),
+            point(4.0, 4.0),
+            point(4.0, 1.0),
+            point(1.0, 1.0),
+            point(1.0, 4.0),
+            point(2.0, 4.0),
+            point(2.0, 5.0),
+            point(0.0, 5.0),
+        ]);
+        let segment = Polygon::new(vec![point(0.0, 0.0), point(1.5, 0.0)]);
+        let sum = minkowski_sum(&ring, &segment);
+        assert_eq!(sum.len(), 1);
+        assert_eq!(sum[0].holes.len(), 1);
+        assert!((area(&sum) - (6.5 * 5.0 - 1.5 * 3.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_convex_decomposition() {
+        // The comb needs a convex piece for each tooth.
+        let comb = comb();
+        let pieces = convex_decomposition(&comb);
+        assert!(pieces.len() <= 4 * 5);
+        for piece in &pieces {
+            let piece = Polygon::new(piece.clone());
+            assert_eq!(piece.orientation(), PolarDirection::CCW);
+            assert_eq!(
+                minkowski_sum_convex(&piece.exterior, &[point(0.0, 0.0)]).len(),
+                piece.exterior.len()
+            );
+        }
+        let total = pieces
+            .into_iter()
+            .map(|piece| Polygon::new(piece).signed_area())
+            .sum::<f64>();
+        assert!((total - comb.signed_area()).abs() < 1e-9);
+    }
+}
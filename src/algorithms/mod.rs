@@ -1,4 +1,47 @@
+mod bentley_ottmann;
+mod bowyer_watson;
+mod chan;
+mod clipping;
+mod constrained_delaunay;
+mod convex_hull_3d;
+mod delaunay_dual;
 mod dewall;
+mod ear_clipping;
+mod fortune;
 mod graham_scan;
+mod jarvis_march;
+mod minkowski_sum;
+mod monotone_chain;
+mod monotone_partition;
+mod polygon_boolean;
+mod quickhull;
+mod ruppert;
 
-pub use crate::algorithms::graham_scan::algorithm::graham_scan;
+pub use crate::algorithms::bentley_ottmann::algorithm::{
+    bentley_ottmann, bentley_ottmann_steps, Intersection, SweepStep,
+};
+pub use crate::algorithms::bowyer_watson::algorithm::bowyer_watson;
+pub use crate::algorithms::chan::algorithm::chan;
+pub use crate::algorithms::clipping::algorithm::{liang_barsky, sutherland_hodgman};
+pub use crate::algorithms::constrained_delaunay::algorithm::{
+    constrained_delaunay, ConstraintError,
+};
+pub use crate::algorithms::convex_hull_3d::algorithm::convex_hull_3d;
+pub use crate::algorithms::delaunay_dual::algorithm::delaunay_dual;
+pub use crate::algorithms::dewall::algorithm::{dewall, dewall_3d};
+pub use crate::algorithms::ear_clipping::algorithm::ear_clipping;
+pub use crate::algorithms::fortune::algorithm::fortune;
+pub use crate::algorithms::graham_scan::algorithm::{graham_scan, graham_scan_polygon};
+pub use crate::algorithms::jarvis_march::algorithm::{
+    jarvis_march, jarvis_march_steps, JarvisStep,
+};
+pub use crate::algorithms::minkowski_sum::algorithm::{
+    convex_decomposition, minkowski_sum, minkowski_sum_convex,
+};
+pub use crate::algorithms::monotone_chain::algorithm::monotone_chain;
+pub use crate::algorithms::monotone_partition::algorithm::{
+    monotone_partition, monotone_triangulation,
+};
+pub use crate::algorithms::polygon_boolean::algorithm::{polygon_boolean, BooleanOperation};
+pub use crate::algorithms::quickhull::algorithm::quickhull;
+pub use crate::algorithms::ruppert::algorithm::{ruppert, RefinementError};
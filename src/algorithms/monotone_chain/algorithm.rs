@@ -0,0 +1,161 @@
+#[cfg(test)]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::data::{Cone, PolarDirection, Pos2, Scalar};
+#[cfg(test)]
+use crate::{algorithms::graham_scan, data::pos2};
+
+// https://en.wikibooks.org/wiki/Algorithm_Implementation/Geometry/Convex_hull/Monotone_chain
+//
+// Returns the hull in the same order as `graham_scan`: counter-clockwise, starting at
+// the point with the lowest y-coordinate (and then the lowest x-coordinate). With
+// `keep_collinear`, points lying on an edge of the hull are part of it as well.
+pub fn monotone_chain<T: Scalar>(points: &[Pos2<T>], keep_collinear: bool) -> Vec<Pos2<T>> {
+    // Sort the points lexicographically, first by x-coordinate and in case of a tie by
+    // y-coordinate.
+    let mut points = points.to_vec();
+    points.sort_by(Pos2::lexicographic);
+    points.dedup();
+
+    // When all points lie on a line, the lower and upper hulls are the same, and
+    // walking both would visit the points in between twice. The hull then goes along
+    // the line from its lowest end.
+    let collinear = match (points.first(), points.last()) {
+        (Some(&first), Some(&last)) => points
+            .iter()
+            .all(|p| turn(first, last, *p) == PolarDirection::Collinear),
+        _ => true,
+    };
+    if collinear {
+        let mut line = match points[..] {
+            [first, _, .., last] if !keep_collinear => vec![first, last],
+            _ => points,
+        };
+        if lowest(&line) != 0 {
+            line.reverse();
+        }
+        return line;
+    }
+
+    // The lower hull runs from left to right and the upper hull back, each making only
+    // left turns (or going straight on, when keeping collinear points). A point that
+    // would make a right turn means that the last point of the chain lies inside the
+    // hull.
+    let chain = |points: &mut dyn Iterator<Item = Pos2<T>>| {
+        let mut chain: Vec<Pos2<T>> = vec![];
+        for point in points {
+            while chain.len() > 1 {
+                let direction = turn(chain[chain.len() - 2], chain[chain.len() - 1], point);
+                match direction {
+                    PolarDirection::CCW => break,
+                    PolarDirection::Collinear if keep_collinear => break,
+                    PolarDirection::Collinear | PolarDirection::CW => chain.pop(),
+                };
+            }
+            chain.push(point);
+        }
+        // The last point of each chain is the first point of the other.
+        chain.pop();
+        chain
+    };
+    let mut hull = chain(&mut points.iter().copied());
+    hull.append(&mut chain(&mut points.iter().rev().copied()));
+
+    // Rotate the hull to start where `graham_scan` does.
+    let start = lowest(&hull);
+    hull.rotate_left(start);
+    hull
+}
+
+/// The index of the point with the lowest y-coordinate (and then the lowest
+/// x-coordinate), where `graham_scan` starts.
+fn lowest<T: Scalar>(points: &[Pos2<T>]) -> usize {
+    (0..points.len())
+        .min_by(|&i, &j| {
+            let (p, q) = (points[i], points[j]);
+            p.y.numeric_cmp(&q.y).then_with(|| p.x.numeric_cmp(&q.x))
+        })
+        .unwrap_or(0)
+}
+
+/// The direction of the turn made going from `a` to `b` to `c`.
+fn turn<T: Scalar>(a: Pos2<T>, b: Pos2<T>, c: Pos2<T>) -> PolarDirection {
+    Cone {
+        a: a.into(),
+        b: c.into(),
+        origin: b.into(),
+    }
+    .angle_direction()
+}
+
+#[test]
+fn test_monotone_chain() {
+    let bottom_left = pos2(-1.0, -1.0);
+    let bottom_right = pos2(1.0, -1.0);
+    let top_right = pos2(1.0, 1.0);
+    let top_left = pos2(-1.0, 1.0);
+    let bottom_middle = pos2(0.0, -1.0);
+    let left_middle = pos2(-1.0, 0.0);
+    let points = vec![
+        top_right,
+        left_middle,
+        pos2(0.0, 0.0),
+        bottom_middle,
+        top_left,
+        bottom_right,
+        bottom_left,
+    ];
+    assert_eq!(
+        monotone_chain(&points, false),
+        vec![bottom_left, bottom_right, top_right, top_left]
+    );
+    assert_eq!(
+        monotone_chain(&points, true),
+        vec![
+            bottom_left,
+            bottom_middle,
+            bottom_right,
+            top_right,
+            top_left,
+            left_middle
+        ]
+    );
+}
+
+#[test]
+fn test_monotone_chain_collinear() {
+    let points = vec![pos2(1.0, 1.0), pos2(-1.0, -1.0), pos2(0.0, 0.0)];
+    assert_eq!(
+        monotone_chain(&points, false),
+        vec![pos2(-1.0, -1.0), pos2(1.0, 1.0)]
+    );
+    assert_eq!(
+        monotone_chain(&points, true),
+        vec![pos2(-1.0, -1.0), pos2(0.0, 0.0), pos2(1.0, 1.0)]
+    );
+    assert_eq!(
+        monotone_chain(&[pos2(0.0, 0.0)], true),
+        vec![pos2(0.0, 0.0)]
+    );
+
+    // A line going down to the right starts at its lower end, as in `graham_scan`.
+    let points = vec![pos2(0.0, 1.0), pos2(1.0, 0.0), pos2(0.5, 0.5)];
+    assert_eq!(monotone_chain(&points, false), graham_scan(&points));
+    assert_eq!(
+        monotone_chain(&points, true),
+        vec![pos2(1.0, 0.0), pos2(0.5, 0.5), pos2(0.0, 1.0)]
+    );
+    let points = vec![pos2(0.0, 1.0), pos2(1.0, 0.0)];
+    assert_eq!(monotone_chain(&points, false), graham_scan(&points));
+}
+
+#[test]
+fn test_matches_graham_scan() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let points = (0..50)
+            .map(|_| pos2(rng.gen_range(-1.0..1.0f32), rng.gen_range(-1.0..1.0)))
+            .collect::<Vec<_>>();
+        assert_eq!(monotone_chain(&points, false), graham_scan(&points));
+    }
+}
@@ -0,0 +1,505 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashSet},
+};
+
+use crate::data::{
+    simplex::{LineSegment, Point, Triangle},
+    PolarDirection, Polygon, Scalar,
+};
+
+// https://en.wikipedia.org/wiki/Polygon_triangulation#Monotone_polygon_triangulation
+//
+// Splits a simple polygon into y-monotone pieces, which any horizontal line crosses at
+// most once, by sweeping a line over it from the top down. Every vertex is classified
+// by its neighbours: a split vertex has both below it and points into the polygon, and
+// a merge vertex has both above it and points out. Those are the only places where the
+// polygon isn't monotone, and each one gets a diagonal, to the lowest vertex above it
+// (for a split vertex) or the highest vertex below it (for a merge vertex) between the
+// edges to its left and right. This takes O(n log n) time for n vertices.
+//
+// Holes are handled the same way: the top of a hole is a split vertex and its bottom a
+// merge vertex, so each hole is connected to the rest of the polygon by diagonals.
+// Vertices at the same height are swept from left to right, as if the line were tilted
+// ever so slightly, so horizontal edges need no special treatment.
+//
+// Returns the diagonals.
+pub fn monotone_partition<T: Scalar>(polygon: &Polygon<T>) -> Vec<LineSegment<T>> {
+    let rings = Rings::new(polygon);
+    let diagonals = rings.partition();
+    rings.segments(&diagonals)
+}
+
+// Triangulates a simple polygon by splitting it into y-monotone pieces, see
+// `monotone_partition`, and then triangulating each piece in linear time. A monotone
+// piece is swept from the top down, keeping a stack of the vertices passed that still
+// need triangles below them. They make up a reflex chain, along one side of the piece,
+// which is cut down from the bottom as soon as diagonals can be drawn to it.
+//
+// The whole takes O(n log n) time for n vertices. Vertices where the boundary goes
+// straight on can be left out of the triangles, rather than making flat ones.
+//
+// Returns the counter-clockwise triangles, and the diagonals between the monotone
+// pieces.
+pub fn monotone_triangulation<T: Scalar>(
+    polygon: &Polygon<T>,
+) -> (Vec<Triangle<T>>, Vec<LineSegment<T>>) {
+    let rings = Rings::new(polygon);
+    let diagonals = rings.partition();
+    let mut triangles = vec![];
+    for piece in rings.pieces(&diagonals) {
+        rings.triangulate(&piece, &mut triangles);
+    }
+    (triangles, rings.segments(&diagonals))
+}
+
+/// The kinds of vertices the sweep comes across, with "below" and "above" relative to
+/// the sweep order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Both neighbours are below, and the interior is below.
+    Start,
+    /// Both neighbours are below, and the interior is above as well.
+    Split,
+    /// Both neighbours are above, and the interior is above.
+    End,
+    /// Both neighbours are above, and the interior is below as well.
+    Merge,
+    /// One neighbour is above and one is below, with the interior to the right.
+    Left,
+    /// One neighbour is above and one is below, with the interior to the left.
+    Right,
+}
+
+/// The vertices of all rings of a polygon, with the exterior counter-clockwise and the
+/// holes clockwise, so the interior is always to the left of an edge.
+struct Rings<T: Scalar> {
+    points: Vec<Point<T>>,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+}
+
+impl<T: Scalar> Rings<T> {
+    fn new(polygon: &Polygon<T>) -> Self {
+        let (mut points, mut next, mut prev) = (vec![], vec![], vec![]);
+        for (i, ring) in polygon.rings().enumerate() {
+            let mut ring = ring.to_vec();
+            let direction = Polygon::new(ring.clone()).orientation();
+            let backwards = if i == 0 {
+                PolarDirection::CW
+            } else {
+                PolarDirection::CCW
+            };
+            if direction == backwards {
+                ring.reverse();
+            }
+            let start = points.len();
+            let n = ring.len();
+            points.extend(ring);
+            next.extend((0..n).map(|k| start + (k + 1) % n));
+            prev.extend((0..n).map(|k| start + (k + n - 1) % n));
+        }
+        Self { points, next, prev }
+    }
+
+    fn kind(&self, v: usize) -> Kind {
+        let p = self.points[v];
+        let [prev, next] = [self.prev[v], self.next[v]].map(|u| self.points[u]);
+        let convex = T::orient2d(prev.into(), p.into(), next.into()) == Ordering::Greater;
+        match (sweep_order(&prev, &p), sweep_order(&next, &p)) {
+            (Ordering::Greater, Ordering::Greater) if convex => Kind::Start,
+            (Ordering::Greater, Ordering::Greater) => Kind::Split,
+            (Ordering::Less, Ordering::Less) if convex => Kind::End,
+            (Ordering::Less, Ordering::Less) => Kind::Merge,
+            // Going down, the interior is to the left of the direction of travel, so to
+            // the right of the vertex.
+            (Ordering::Less, _) => Kind::Left,
+            _ => Kind::Right,
+        }
+    }
+
+    /// The edge from `v` to the next vertex.
+    fn edge(&self, v: usize) -> Edge<T> {
+        let [a, b] = [v, self.next[v]].map(|u| self.points[u]);
+        match sweep_order(&a, &b) {
+            Ordering::Greater => Edge([b, a]),
+            _ => Edge([a, b]),
+        }
+    }
+
+    /// Sweeps over the rings, returning the diagonals that split them into monotone
+    /// pieces as pairs of vertices.
+    fn partition(&self) -> Vec<[usize; 2]> {
+        let mut events = (0..self.points.len()).collect::<Vec<_>>();
+        events.sort_by(|&u, &v| sweep_order(&self.points[u], &self.points[v]));
+
+        // The edges the sweep line crosses which have the interior to their right, from
+        // left to right, each with its helper: the lowest vertex above the sweep line
+        // between that edge and the next one, which a diagonal can connect to.
+        let mut status = BTreeMap::<Edge<T>, usize>::new();
+        let mut diagonals = vec![];
+        let is_merge = |v: usize| self.kind(v) == Kind::Merge;
+        for v in events {
+            let kind = self.kind(v);
+            // The edge from the previous vertex, which ends at this one if it's in the
+            // status.
+            let incoming = self.edge(self.prev[v]);
+            if matches!(kind, Kind::End | Kind::Merge | Kind::Left) {
+                if let Some(helper) = status.remove(&incoming) {
+                    if is_merge(helper) {
+                        diagonals.push([helper, v]);
+                    }
+                }
+            }
+            if matches!(kind, Kind::Split | Kind::Merge | Kind::Right) {
+                // The edge directly to the left of the vertex.
+                let probe = Edge([self.points[v]; 2]);
+                if let Some((_, helper)) = status.range_mut(..probe).next_back() {
+                    if kind == Kind::Split || is_merge(*helper) {
+                        diagonals.push([*helper, v]);
+                    }
+                    *helper = v;
+                }
+            }
+            if matches!(kind, Kind::Start | Kind::Split | Kind::Left) {
+                status.insert(self.edge(v), v);
+            }
+        }
+        diagonals
+    }
+
+    /// Splits the rings along the diagonals, into pieces given by their vertices in
+    /// counter-clockwise order.
+    fn pieces(&self, diagonals: &[[usize; 2]]) -> Vec<Vec<usize>> {
+        // The vertices connected to each vertex, in counter-clockwise order around it.
+        let mut neighbours = (0..self.points.len())
+            .map(|v| vec![self.prev[v], self.next[v]])
+            .collect::<Vec<_>>();
+        for &[u, v] in diagonals {
+            neighbours[u].push(v);
+            neighbours[v].push(u);
+        }
+        for (v, neighbours) in neighbours.iter_mut().enumerate() {
+            let p = self.points[v];
+            neighbours.sort_by(|&a, &b| angular(p, self.points[a], self.points[b]));
+        }
+
+        // Walk around each piece keeping it to the left, by turning as sharply to the
+        // left as possible at every vertex.
+        let starts = (0..self.points.len())
+            .map(|v| [v, self.next[v]])
+            .chain(diagonals.iter().flat_map(|&[u, v]| [[u, v], [v, u]]));
+        let mut visited = HashSet::new();
+        let mut pieces = vec![];
+        for start in starts {
+            let mut piece = vec![];
+            let [mut u, mut v] = start;
+            while visited.insert([u, v]) {
+                piece.push(u);
+                let around = &neighbours[v];
+                let k = around.iter().position(|&w| w == u).unwrap();
+                let w = around[(k + around.len() - 1) % around.len()];
+                [u, v] = [v, w];
+            }
+            if !piece.is_empty() {
+                pieces.push(piece);
+            }
+        }
+        pieces
+    }
+
+    /// Triangulates a monotone piece, adding its triangles to `triangles`.
+    fn triangulate(&self, piece: &[usize], triangles: &mut Vec<Triangle<T>>) {
+        let n = piece.len();
+        if n < 3 {
+            return;
+        }
+        let point = |k: usize| self.points[piece[k]];
+        let order = |j: &usize, k: &usize| sweep_order(&point(*j), &point(*k));
+        let top = (0..n).min_by(order).unwrap();
+        let bottom = (0..n).max_by(order).unwrap();
+
+        // Going around the piece counter-clockwise from the top leads down its left
+        // side, and going clockwise down its right side. Merging the two gives the
+        // vertices in sweep order, along with the side they're on.
+        let mut sorted = vec![(top, true)];
+        let (mut left, mut right) = ((top + 1) % n, (top + n - 1) % n);
+        loop {
+            let take_left = match (left == bottom, right == bottom) {
+                (true, true) => break,
+                (true, false) => false,
+                (false, true) => true,
+                (false, false) => order(&left, &right) == Ordering::Less,
+            };
+            if take_left {
+                sorted.push((left, true));
+                left = (left + 1) % n;
+            } else {
+                sorted.push((right, false));
+                right = (right + n - 1) % n;
+            }
+        }
+        sorted.push((bottom, true));
+
+        let mut emit = |a: usize, b: usize, c: usize| {
+            let [a, b, c] = [a, b, c].map(point);
+            match T::orient2d(a.into(), b.into(), c.into()) {
+                Ordering::Greater => triangles.push(Triangle {
+                    vertices: [a, b, c],
+                }),
+                Ordering::Less => triangles.push(Triangle {
+                    vertices: [a, c, b],
+                }),
+                Ordering::Equal => {}
+            }
+        };
+        let mut stack = vec![sorted[0], sorted[1]];
+        for &(v, is_left) in &sorted[2..n - 1] {
+            let (_, top_is_left) = *stack.last().unwrap();
+            if is_left != top_is_left {
+                // The vertex sees the whole chain on the other side.
+                for pair in stack.windows(2) {
+                    emit(v, pair[0].0, pair[1].0);
+                }
+                stack = vec![*stack.last().unwrap(), (v, is_left)];
+            } else {
+                // Cut off the bottom of the chain as long as it bends away from the
+                // vertex.
+                let mut last = stack.pop().unwrap();
+                while let Some(&(above, _)) = stack.last() {
+                    let [a, b, c] = [above, last.0, v].map(point);
+                    let turn = if is_left {
+                        T::orient2d(a.into(), b.into(), c.into())
+                    } else {
+                        T::orient2d(c.into(), b.into(), a.into())
+                    };
+                    if turn != Ordering::Greater {
+                        break;
+                    }
+                    emit(above, last.0, v);
+                    last = stack.pop().unwrap();
+                }
+                stack.push(last);
+                stack.push((v, is_left));
+            }
+        }
+        for pair in stack.windows(2) {
+            emit(bottom, pair[0].0, pair[1].0);
+        }
+    }
+
+    fn segments(&self, diagonals: &[[usize; 2]]) -> Vec<LineSegment<T>> {
+        diagonals
+            .iter()
+            .map(|&[u, v]| LineSegment {
+                vertices: [self.points[u], self.points[v]],
+            })
+            .collect()
+    }
+}
+
+/// The order the sweep comes across points in: from the top down, and from left to
+/// right at the same height.
+fn sweep_order<T: Scalar>(p: &Point<T>, q: &Point<T>) -> Ordering {
+    q.y.numeric_cmp(&p.y).then_with(|| p.x.numeric_cmp(&q.x))
+}
+
+/// How the directions from `p` to `a` and to `b` are ordered, counter-clockwise from
+/// the positive x-axis.
+fn angular<T: Scalar>(p: Point<T>, a: Point<T>, b: Point<T>) -> Ordering {
+    // Whether a direction points into the lower half, starting at the negative x-axis.
+    let lower = |q: Point<T>| q.y < p.y || (q.y == p.y && q.x < p.x);
+    lower(a)
+        .cmp(&lower(b))
+        .then_with(|| T::orient2d(p.into(), b.into(), a.into()))
+}
+
+/// An edge in the status of the sweep, from its upper to its lower endpoint. Edges are
+/// ordered from left to right where the sweep line crosses them, which holds still as
+/// the line moves since the edges of a simple polygon don't cross. An edge with both
+/// endpoints the same is a probe, ordered relative to the others by its point.
+#[derive(Debug, Clone, Copy)]
+struct Edge<T: Scalar>([Point<T>; 2]);
+
+impl<T: Scalar> Edge<T> {
+    /// How the edge is ordered relative to a point on the sweep line.
+    fn position(&self, p: Point<T>) -> Ordering {
+        let [a, b] = self.0;
+        // Going down the edge, points to the right are to the left of the direction
+        // of travel.
+        T::orient2d(a.into(), b.into(), p.into()).reverse()
+    }
+}
+
+impl<T: Scalar> PartialEq for Edge<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Scalar> Eq for Edge<T> {}
+
+impl<T: Scalar> PartialOrd for Edge<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Scalar> Ord for Edge<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.0 == other.0 {
+            return Ordering::Equal;
+        }
+        // Compare the endpoints of the edge the sweep came across last to the other
+        // edge, which spans the height of at least its upper endpoint.
+        let [a, b] = self.0;
+        let [c, d] = other.0;
+        if sweep_order(&c, &a) == Ordering::Less {
+            other.position(a).then_with(|| other.position(b)).reverse()
+        } else {
+            self.position(c).then_with(|| self.position(d))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_rational::Rational64;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{
+        algorithms::ear_clipping,
+        data::polygon::tests::{check_triangulation, star},
+    };
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    /// Checks that every piece is monotone: going around it, it goes down once and up
+    /// once.
+    fn check_monotone<T: Scalar>(rings: &Rings<T>, pieces: &[Vec<usize>]) {
+        for piece in pieces {
+            let turns = (0..piece.len())
+                .filter(|&k| {
+                    let [u, v, w] = [k, k + 1, k + 2].map(|j| rings.points[piece[j % piece.len()]]);
+                    sweep_order(&u, &v) != sweep_order(&v, &w)
+                })
+                .count();
+            assert_eq!(turns, 2, "{piece:?}");
+        }
+    }
+
+    #[test]
+    fn test_convex() {
+        let square = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(1.0, 1.0),
+            point(0.0, 1.0),
+        ]);
+        let (triangles, diagonals) = monotone_triangulation(&square);
+        assert!(diagonals.is_empty());
+        check_triangulation(&square, &triangles);
+    }
+
+    #[test]
+    fn test_split_and_merge() {
+        // An hourglass on its side, with a notch in the top and in the bottom: a merge
+        // vertex and a split vertex.
+        let polygon = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(2.0, 1.0),
+            point(4.0, 0.0),
+            point(4.0, 4.0),
+            point(2.0, 3.0),
+            point(0.0, 4.0),
+        ]);
+        let (triangles, diagonals) = monotone_triangulation(&polygon);
+        assert_eq!(
+            diagonals,
+            vec![LineSegment {
+                vertices: [point(2.0, 3.0), point(2.0, 1.0)]
+            }]
+        );
+        check_triangulation(&polygon, &triangles);
+    }
+
+    #[test]
+    fn test_rectilinear() {
+        // A staircase with horizontal edges at the same heights as each other, which
+        // the tilted sweep has to get right, and a square hole.
+        let polygon = Polygon {
+            exterior: vec![
+                point(0.0, 0.0),
+                point(6.0, 0.0),
+                point(6.0, 2.0),
+                point(5.0, 2.0),
+                point(5.0, 4.0),
+                point(4.0, 4.0),
+                point(4.0, 2.0),
+                point(2.0, 2.0),
+                point(2.0, 4.0),
+                point(1.0, 4.0),
+                point(1.0, 2.0),
+                point(0.0, 2.0),
+            ],
+            holes: vec![vec![
+                point(2.0, 0.5),
+                point(2.0, 1.5),
+                point(4.0, 1.5),
+                point(4.0, 0.5),
+            ]],
+        };
+        let rings = Rings::new(&polygon);
+        check_monotone(&rings, &rings.pieces(&rings.partition()));
+        let (triangles, _) = monotone_triangulation(&polygon);
+        check_triangulation(&polygon, &triangles);
+    }
+
+    #[test]
+    fn test_random() {
+        // Star-shaped polygons around the origin, with a star-shaped hole in the middle,
+        // which have plenty of split and merge vertices.
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let origin = point(0.0, 0.0);
+            let polygon = Polygon {
+                exterior: star(&mut rng, 100, origin, 1.0),
+                holes: vec![star(&mut rng, 30, origin, 0.4)],
+            };
+            let rings = Rings::new(&polygon);
+            check_monotone(&rings, &rings.pieces(&rings.partition()));
+            let (triangles, _) = monotone_triangulation(&polygon);
+            check_triangulation(&polygon, &triangles);
+            assert_eq!(triangles.len(), ear_clipping(&polygon).len());
+        }
+    }
+
+    #[test]
+    fn test_exact() {
+        // A comb with its teeth pointing down, on a small integer grid, triangulated
+        // exactly.
+        let r = |x: i64, y: i64| Point {
+            x: Rational64::from_integer(x),
+            y: Rational64::from_integer(y),
+        };
+        let mut exterior = vec![r(0, 4), r(0, 0)];
+        for i in 0..4 {
+            exterior.extend([r(2 * i + 1, 3), r(2 * i + 2, 0)]);
+        }
+        exterior.push(r(8, 4));
+        let polygon = Polygon::new(exterior);
+        let (triangles, _) = monotone_triangulation(&polygon);
+        let area = triangles
+            .iter()
+            .fold(Rational64::from_integer(0), |sum, t| {
+                let [a, b, c] = t.vertices;
+                sum + ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y))
+                    / Rational64::from_integer(2)
+            });
+        assert_eq!(area, polygon.signed_area());
+        assert_eq!(triangles.len(), polygon.exterior.len() - 2);
+    }
+}
@@ -0,0 +1,461 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashSet},
+};
+
+use crate::{
+    algorithms::bentley_ottmann,
+    data::{
+        simplex::{Lexicographic, LineSegment, Point},
+        PointLocation, Polygon, Scalar,
+    },
+};
+
+/// How two polygons are combined by [`polygon_boolean`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOperation {
+    /// Everything in either polygon.
+    Union,
+    /// Everything in both polygons.
+    Intersection,
+    /// Everything in the first polygon but not in the second.
+    Difference,
+    /// Everything in exactly one of the polygons.
+    Xor,
+}
+
+// https://doi.org/10.1016/j.advengsoft.2013.04.004 (Martinez, Rueda and Feito)
+//
+// Combines two polygons, with holes, into the polygons making up the result. The edges
+// of both are split wherever they meet the other polygon, using `bentley_ottmann`,
+// after which every piece of an edge lies either inside or outside the other polygon,
+// or on an edge of it. The operation decides which pieces bound the result, as in
+// Martinez-Rueda, and those are linked back up into rings.
+//
+// Edges the polygons share are pieces lying on an edge of both. They bound the result
+// when the polygons are on the same side of them for a union or intersection, and on
+// opposite sides for a difference. Rings only touching at a vertex are kept apart, by
+// always taking the sharpest turn to the left at a vertex where several rings meet.
+//
+// Every piece is classified against every edge of the other polygon, so this takes
+// O(nm) time for polygons with n and m vertices, on top of the sweep. The rings of the
+// result go counter-clockwise around the exteriors and clockwise around the holes.
+pub fn polygon_boolean<T: Scalar>(
+    a: &Polygon<T>,
+    b: &Polygon<T>,
+    operation: BooleanOperation,
+) -> Vec<Polygon<T>> {
+    let polygons = [a, b];
+
+    // Every edge of both polygons, going around with the interior to its left.
+    let mut edges = vec![];
+    let mut segments = vec![];
+    for (k, polygon) in polygons.iter().enumerate() {
+        for (i, ring) in polygon.rings().enumerate() {
+            let mut ring = ring.to_vec();
+            ring.dedup();
+            if ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            if ring.len() < 3 {
+                continue;
+            }
+            let area = Polygon::new(ring.clone()).signed_area();
+            if (i == 0) == (area < T::zero()) {
+                ring.reverse();
+            }
+            for j in 0..ring.len() {
+                edges.push(k);
+                segments.push(LineSegment {
+                    vertices: [ring[j], ring[(j + 1) % ring.len()]],
+                });
+            }
+        }
+    }
+
+    // Split the edges where they meet, numbering the points they're split into.
+    let mut splits = vec![vec![]; segments.len()];
+    for intersection in bentley_ottmann(&segments) {
+        for s in intersection.segments {
+            splits[s].push(intersection.point);
+        }
+    }
+    let mut ids = BTreeMap::new();
+    let mut points = vec![];
+    let mut id = |p: Point<T>| {
+        *ids.entry(Lexicographic(p)).or_insert_with(|| {
+            points.push(p);
+            points.len() - 1
+        })
+    };
+    let mut pieces: [Vec<[usize; 2]>; 2] = [vec![], vec![]];
+    for ((k, segment), mut splits) in edges.into_iter().zip(&segments).zip(splits) {
+        let [start, end] = segment.vertices;
+        let along = |p: &Point<T>| {
+            (p.x - start.x) * (end.x - start.x) + (p.y - start.y) * (end.y - start.y)
+        };
+        splits.sort_by(|p, q| along(p).total_cmp(&along(q)));
+        let mut previous = id(start);
+        for p in splits.into_iter().chain([end]) {
+            let current = id(p);
+            if current != previous {
+                pieces[k].push([previous, current]);
+                previous = current;
+            }
+        }
+    }
+
+    // Decide which pieces bound the result, and which way around they go.
+    let sets = pieces
+        .clone()
+        .map(|pieces| pieces.into_iter().collect::<HashSet<_>>());
+    let mut boundary = vec![];
+    for (k, pieces) in pieces.iter().enumerate() {
+        let other = polygons[1 - k];
+        for &[u, v] in pieces {
+            let side = if sets[1 - k].contains(&[u, v]) {
+                Side::Same
+            } else if sets[1 - k].contains(&[v, u]) {
+                Side::Opposite
+            } else {
+                let two = T::from_f64(2.0);
+                let (p, q) = (points[u], points[v]);
+                let midpoint = Point {
+                    x: (p.x + q.x) / two,
+                    y: (p.y + q.y) / two,
+                };
+                // Rounding can leave a piece running along an edge of the other polygon
+                // without sharing its endpoints, which is taken to be outside of it.
+                match other.locate(midpoint) {
+                    PointLocation::Inside => Side::Inside,
+                    PointLocation::OnBoundary | PointLocation::Outside => Side::Outside,
+                }
+            };
+            let keep = match (operation, k, side) {
+                (BooleanOperation::Union, _, Side::Outside) => Some([u, v]),
+                (BooleanOperation::Intersection, _, Side::Inside) => Some([u, v]),
+                (BooleanOperation::Union | BooleanOperation::Intersection, 0, Side::Same) => {
+                    Some([u, v])
+                }
+                (BooleanOperation::Difference, 0, Side::Outside | Side::Opposite) => Some([u, v]),
+                (BooleanOperation::Difference, 1, Side::Inside) => Some([v, u]),
+                (BooleanOperation::Xor, _, Side::Outside) => Some([u, v]),
+                (BooleanOperation::Xor, _, Side::Inside) => Some([v, u]),
+                _ => None,
+            };
+            boundary.extend(keep);
+        }
+    }
+
+    let rings = link(&points, &boundary);
+    assemble(rings)
+}
+
+/// Where a piece of an edge of one polygon lies relative to the other polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Inside,
+    Outside,
+    /// On an edge of the other polygon going the same way.
+    Same,
+    /// On an edge of the other polygon going the other way.
+    Opposite,
+}
+
+/// Links the directed edges into rings, turning as sharply to the left as possible
+/// wherever there's a choice.
+fn link<T: Scalar>(points: &[Point<T>], edges: &[[usize; 2]]) -> Vec<Vec<Point<T>>> {
+    let mut outgoing = vec![vec![]; points.len()];
+    for (e, &[u, _]) in edges.iter().enumerate() {
+        outgoing[u].push(e);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut rings = vec![];
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        let mut ring = vec![];
+        let mut e = start;
+        loop {
+            used[e] = true;
+            let [u, v] = edges[e];
+            ring.push(points[u]);
+            if v == edges[start][0] {
+                rings.push(ring);
+                break;
+            }
+            let (origin, back) = (points[v], points[u]);
+            let next = outgoing[v]
+                .iter()
+                .copied()
+                .filter(|&f| !used[f])
+                .min_by(|&f, &g| clockwise(origin, back, points[edges[f][1]], points[edges[g][1]]));
+            match next {
+                Some(next) => e = next,
+                // The edges don't close up, which only rounding can cause.
+                None => break,
+            }
+        }
+    }
+    rings
+}
+
+/// How the directions from `origin` to `p` and to `q` are ordered, going clockwise
+/// from the direction to `back`, which comes last.
+fn clockwise<T: Scalar>(origin: Point<T>, back: Point<T>, p: Point<T>, q: Point<T>) -> Ordering {
+    let quadrant = |r: Point<T>| match T::orient2d(origin.into(), back.into(), r.into()) {
+        Ordering::Less => 0,
+        Ordering::Greater => 2,
+        Ordering::Equal => {
+            let dot =
+                (back.x - origin.x) * (r.x - origin.x) + (back.y - origin.y) * (r.y - origin.y);
+            if dot < T::zero() {
+                1
+            } else {
+                3
+            }
+        }
+    };
+    quadrant(p)
+        .cmp(&quadrant(q))
+        .then_with(|| T::orient2d(origin.into(), p.into(), q.into()))
+}
+
+/// Sorts the rings into exteriors, going counter-clockwise, and holes, going clockwise,
+/// and puts each hole into the smallest exterior around it.
+fn assemble<T: Scalar>(rings: Vec<Vec<Point<T>>>) -> Vec<Polygon<T>> {
+    let (mut exteriors, mut holes) = (vec![], vec![]);
+    for ring in rings {
+        let area = Polygon::new(ring.clone()).signed_area();
+        match area.numeric_cmp(&T::zero()) {
+            Ordering::Greater => exteriors.push((area, Polygon::new(ring))),
+            Ordering::Less => holes.push(ring),
+            // Flat rings, which only bound the result on both sides at once.
+            Ordering::Equal => {}
+        }
+    }
+    for hole in holes {
+        let around = exteriors
+            .iter_mut()
+            .filter(|(_, exterior)| contains(exterior, &hole))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b));
+        if let Some((_, exterior)) = around {
+            exterior.holes.push(hole);
+        }
+    }
+    exteriors.into_iter().map(|(_, polygon)| polygon).collect()
+}
+
+/// Whether the ring lies inside the polygon, which it doesn't cross but can touch.
+fn contains<T: Scalar>(polygon: &Polygon<T>, ring: &[Point<T>]) -> bool {
+    let two = T::from_f64(2.0);
+    let midpoints = (0..ring.len()).map(|i| {
+        let (p, q) = (ring[i], ring[(i + 1) % ring.len()]);
+        Point {
+            x: (p.x + q.x) / two,
+            y: (p.y + q.y) / two,
+        }
+    });
+    ring.iter()
+        .copied()
+        .chain(midpoints)
+        .map(|p| polygon.locate(p))
+        .find(|location| *location != PointLocation::OnBoundary)
+        == Some(PointLocation::Inside)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_rational::Rational64;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::data::polygon::tests::star;
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    fn rectangle<T: Scalar>(min: [T; 2], max: [T; 2]) -> Polygon<T> {
+        Polygon::new(vec![
+            Point {
+                x: min[0],
+                y: min[1],
+            },
+            Point {
+                x: max[0],
+                y: min[1],
+            },
+            Point {
+                x: max[0],
+                y: max[1],
+            },
+            Point {
+                x: min[0],
+                y: max[1],
+            },
+        ])
+    }
+
+    fn area<T: Scalar>(polygons: &[Polygon<T>]) -> T {
+        polygons
+            .iter()
+            .fold(T::zero(), |sum, polygon| sum + polygon.signed_area())
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let a = rectangle([0.0, 0.0], [2.0, 2.0]);
+        let b = rectangle([1.0, 1.0], [3.0, 3.0]);
+        let union = polygon_boolean(&a, &b, BooleanOperation::Union);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].exterior.len(), 8);
+        assert_eq!(area(&union), 7.0);
+        let intersection = polygon_boolean(&a, &b, BooleanOperation::Intersection);
+        assert_eq!(intersection.len(), 1);
+        assert_eq!(intersection[0].exterior.len(), 4);
+        assert_eq!(area(&intersection), 1.0);
+        let difference = polygon_boolean(&a, &b, BooleanOperation::Difference);
+        assert_eq!(area(&difference), 3.0);
+        let xor = polygon_boolean(&a, &b, BooleanOperation::Xor);
+        assert_eq!(xor.len(), 2);
+        assert_eq!(area(&xor), 6.0);
+    }
+
+    #[test]
+    fn test_shared_edge() {
+        // Side by side, sharing an edge going opposite ways.
+        let a = rectangle([0.0, 0.0], [1.0, 1.0]);
+        let b = rectangle([1.0, 0.0], [2.0, 1.0]);
+        let union = polygon_boolean(&a, &b, BooleanOperation::Union);
+        assert_eq!(union.len(), 1);
+        assert_eq!(area(&union), 2.0);
+        assert!(polygon_boolean(&a, &b, BooleanOperation::Intersection).is_empty());
+        assert_eq!(
+            polygon_boolean(&a, &b, BooleanOperation::Difference),
+            vec![a.clone()]
+        );
+        assert_eq!(area(&polygon_boolean(&a, &b, BooleanOperation::Xor)), 2.0);
+
+        // One on top of the other, sharing part of an edge going the same way.
+        let b = rectangle([0.5, 0.0], [2.0, 0.5]);
+        let union = polygon_boolean(&a, &b, BooleanOperation::Union);
+        assert_eq!(union.len(), 1);
+        assert_eq!(area(&union), 1.5);
+        let intersection = polygon_boolean(&a, &b, BooleanOperation::Intersection);
+        assert_eq!(area(&intersection), 0.25);
+        assert_eq!(
+            area(&polygon_boolean(&a, &b, BooleanOperation::Difference)),
+            0.75
+        );
+        assert_eq!(area(&polygon_boolean(&a, &b, BooleanOperation::Xor)), 1.25);
+    }
+
+    #[test]
+    fn test_identical() {
+        let a = rectangle([0.0, 0.0], [1.0, 1.0]);
+        // The same square, going the other way around from another vertex.
+        let mut b = a.clone();
+        b.exterior.reverse();
+        b.exterior.rotate_left(1);
+        assert_eq!(
+            polygon_boolean(&a, &b, BooleanOperation::Union),
+            vec![a.clone()]
+        );
+        assert_eq!(
+            polygon_boolean(&a, &b, BooleanOperation::Intersection),
+            vec![a.clone()]
+        );
+        assert!(polygon_boolean(&a, &b, BooleanOperation::Difference).is_empty());
+        assert!(polygon_boolean(&a, &b, BooleanOperation::Xor).is_empty());
+    }
+
+    #[test]
+    fn test_shared_vertex() {
+        // Touching at a corner, which the union keeps as two polygons.
+        let a = rectangle([0.0, 0.0], [1.0, 1.0]);
+        let b = rectangle([1.0, 1.0], [2.0, 2.0]);
+        let union = polygon_boolean(&a, &b, BooleanOperation::Union);
+        assert_eq!(union, vec![a.clone(), b.clone()]);
+        assert!(polygon_boolean(&a, &b, BooleanOperation::Intersection).is_empty());
+    }
+
+    #[test]
+    fn test_holes() {
+        // A frame, and a square over its hole and its right side.
+        let mut frame = rectangle([0.0, 0.0], [4.0, 4.0]);
+        frame.holes.push(vec![
+            point(1.0, 1.0),
+            point(1.0, 3.0),
+            point(3.0, 3.0),
+            point(3.0, 1.0),
+        ]);
+        let b = rectangle([2.0, 1.5], [5.0, 2.5]);
+        let union = polygon_boolean(&frame, &b, BooleanOperation::Union);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].holes.len(), 1);
+        assert_eq!(area(&union), 14.0);
+        let intersection = polygon_boolean(&frame, &b, BooleanOperation::Intersection);
+        assert_eq!(area(&intersection), 1.0);
+
+        // A square inside the hole, which becomes an island.
+        let island = rectangle([1.5, 1.5], [2.5, 2.5]);
+        let union = polygon_boolean(&frame, &island, BooleanOperation::Union);
+        assert_eq!(union.len(), 2);
+        assert_eq!(area(&union), 13.0);
+        assert!(polygon_boolean(&frame, &island, BooleanOperation::Intersection).is_empty());
+        let difference = polygon_boolean(&island, &frame, BooleanOperation::Difference);
+        assert_eq!(difference, vec![island.clone()]);
+    }
+
+    #[test]
+    fn test_exact() {
+        // A triangle and a rectangle crossing at points that aren't on the grid.
+        let r = |x: i64, y: i64| Point {
+            x: Rational64::from_integer(x),
+            y: Rational64::from_integer(y),
+        };
+        let a = Polygon::new(vec![r(0, 0), r(7, 1), r(2, 5)]);
+        let b = rectangle([r(1, 1).x, r(1, 1).y], [r(6, 3).x, r(6, 3).y]);
+        let [union, intersection, difference, xor] = [
+            BooleanOperation::Union,
+            BooleanOperation::Intersection,
+            BooleanOperation::Difference,
+            BooleanOperation::Xor,
+        ]
+        .map(|operation| area(&polygon_boolean(&a, &b, operation)));
+        assert_eq!(union + intersection, a.signed_area() + b.signed_area());
+        assert_eq!(difference, a.signed_area() - intersection);
+        assert_eq!(xor, union - intersection);
+    }
+
+    #[test]
+    fn test_random() {
+        // Random star-shaped polygons around nearby centers, checked against each other
+        // by their areas.
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let mut star = |n: usize| {
+                let center = point(rng.gen_range(-0.5..0.5), rng.gen_range(-0.5..0.5));
+                star(&mut rng, n, center, 1.0)
+            };
+            let a = Polygon::new(star(20));
+            let b = Polygon::new(star(20));
+            let [union, intersection, difference, xor] = [
+                BooleanOperation::Union,
+                BooleanOperation::Intersection,
+                BooleanOperation::Difference,
+                BooleanOperation::Xor,
+            ]
+            .map(|operation| area(&polygon_boolean(&a, &b, operation)));
+            let close = |x: f64, y: f64| (x - y).abs() < 1e-9;
+            assert!(close(
+                union + intersection,
+                a.signed_area() + b.signed_area()
+            ));
+            assert!(close(difference, a.signed_area() - intersection));
+            assert!(close(xor, union - intersection));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(test)]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::data::{Cone, PolarDirection, Pos2, Scalar};
+#[cfg(test)]
+use crate::{algorithms::graham_scan, data::pos2};
+
+// https://en.wikipedia.org/wiki/Quickhull
+//
+// Returns the hull in the same order as `graham_scan`: counter-clockwise, starting at
+// the point with the lowest y-coordinate (and then the lowest x-coordinate).
+pub fn quickhull<T: Scalar>(points: &[Pos2<T>]) -> Vec<Pos2<T>> {
+    // The lexicographically smallest and largest points are certainly on the hull, and
+    // the line through them splits the rest into the lower and upper hull.
+    let (Some(left), Some(right)) = (
+        points.iter().copied().min_by(Pos2::lexicographic),
+        points.iter().copied().max_by(Pos2::lexicographic),
+    ) else {
+        return vec![];
+    };
+    if left == right {
+        return vec![left];
+    }
+
+    let (lower, upper): (Vec<_>, Vec<_>) = points
+        .iter()
+        .copied()
+        .filter(|p| orientation(left, right, *p) != PolarDirection::Collinear)
+        .partition(|p| orientation(left, right, *p) == PolarDirection::CW);
+
+    let mut hull = vec![left];
+    find_hull(left, right, lower, &mut hull);
+    hull.push(right);
+    find_hull(right, left, upper, &mut hull);
+
+    // Rotate the hull to start where `graham_scan` does.
+    let start = hull
+        .iter()
+        .enumerate()
+        .min_by(|(_, p1), (_, p2)| {
+            p1.y.numeric_cmp(&p2.y)
+                .then_with(|| p1.x.numeric_cmp(&p2.x))
+        })
+        .map(|(i, _)| i)
+        .unwrap();
+    hull.rotate_left(start);
+    hull
+}
+
+/// Appends the hull points between `a` and `b`, out of `points`, which all lie to the
+/// right of a -> b.
+fn find_hull<T: Scalar>(a: Pos2<T>, b: Pos2<T>, points: Vec<Pos2<T>>, hull: &mut Vec<Pos2<T>>) {
+    // The point farthest from the line is on the hull. Of several points equally far
+    // away (which lie on a line parallel to a -> b) the outermost one is, while the
+    // others would end up as collinear points on the hull.
+    let Some(c) = points.iter().copied().max_by(|p, q| {
+        area(a, b, *p)
+            .total_cmp(&area(a, b, *q))
+            .then_with(|| p.lexicographic(q))
+    }) else {
+        return;
+    };
+
+    // Points inside the triangle a, c, b aren't on the hull, the rest lie to the right
+    // of either a -> c or c -> b.
+    let (ac, cb): (Vec<_>, Vec<_>) = points
+        .into_iter()
+        .filter(|p| {
+            orientation(a, c, *p) == PolarDirection::CW
+                || orientation(c, b, *p) == PolarDirection::CW
+        })
+        .partition(|p| orientation(a, c, *p) == PolarDirection::CW);
+
+    find_hull(a, c, ac, hull);
+    hull.push(c);
+    find_hull(c, b, cb, hull);
+}
+
+/// Twice the signed area of the triangle a, b, p, positive when p lies to the right of
+/// a -> b. That makes it proportional to the distance of p from the line through them.
+fn area<T: Scalar>(a: Pos2<T>, b: Pos2<T>, p: Pos2<T>) -> T {
+    let ab = b - a;
+    let ap = p - a;
+    ap.x * ab.y - ap.y * ab.x
+}
+
+/// The direction of the turn made going from `a` to `b` to `p`.
+fn orientation<T: Scalar>(a: Pos2<T>, b: Pos2<T>, p: Pos2<T>) -> PolarDirection {
+    Cone {
+        a: a.into(),
+        b: p.into(),
+        origin: b.into(),
+    }
+    .angle_direction()
+}
+
+#[test]
+fn test_quickhull() {
+    // The fixtures of `graham_scan`.
+    let bottom_left = pos2(-1.0, -1.0);
+    let bottom_right = pos2(1.0, -1.0);
+    let top_middle = pos2(0.0, 1.0);
+    let points = vec![bottom_left, bottom_right, top_middle, pos2(0.0, 0.0)];
+    assert_eq!(quickhull(&points), graham_scan(&points));
+    assert_eq!(
+        quickhull(&points),
+        vec![bottom_left, bottom_right, top_middle]
+    );
+
+    let p0 = pos2(-0.41119027, -0.31959605);
+    let p1 = pos2(-0.033056736, -0.1505971);
+    let p2 = pos2(0.22698152, 0.4522189);
+    let p3 = pos2(-0.034094572, 0.35310435);
+    let p4 = pos2(-0.3797356, 0.35341442);
+    let points = vec![p3, p1, p0, p4, p2];
+    assert_eq!(quickhull(&points), graham_scan(&points));
+    assert_eq!(quickhull(&points), vec![p0, p1, p2, p4]);
+
+    let p0 = pos2(0.2, -0.3);
+    let p1 = pos2(-0.45289695, -0.099212766);
+    let p2 = pos2(-0.18725193, -0.058339);
+    let p3 = pos2(-0.26800287, 0.27599692);
+    let p4 = pos2(0.03216493, 0.38522828);
+    let points = vec![p3, p1, p0, p4, p2];
+    assert_eq!(quickhull(&points), graham_scan(&points));
+    assert_eq!(quickhull(&points), vec![p0, p4, p3, p1]);
+}
+
+#[test]
+fn test_quickhull_collinear() {
+    // Points on a hull edge, or on a line parallel to it, are left out.
+    let points = vec![
+        pos2(0.0, 0.0),
+        pos2(4.0, 0.0),
+        pos2(1.0, -1.0),
+        pos2(2.0, -1.0),
+        pos2(3.0, -1.0),
+        pos2(2.0, 2.0),
+        pos2(1.0, 1.0),
+    ];
+    assert_eq!(
+        quickhull(&points),
+        vec![
+            pos2(1.0, -1.0),
+            pos2(3.0, -1.0),
+            pos2(4.0, 0.0),
+            pos2(2.0, 2.0),
+            pos2(0.0, 0.0)
+        ]
+    );
+    assert_eq!(quickhull(&points), graham_scan(&points));
+
+    let points = vec![pos2(1.0, 1.0), pos2(-1.0, -1.0), pos2(0.0, 0.0)];
+    assert_eq!(quickhull(&points), graham_scan(&points));
+}
+
+#[test]
+fn test_matches_graham_scan() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let points = (0..50)
+            .map(|_| pos2(rng.gen_range(-1.0..1.0f32), rng.gen_range(-1.0..1.0)))
+            .collect::<Vec<_>>();
+        assert_eq!(quickhull(&points), graham_scan(&points));
+    }
+}
@@ -0,0 +1,409 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    algorithms::constrained_delaunay::algorithm::{ConstraintError, Triangulation},
+    data::{
+        simplex::{LineSegment, Point, Simplex, Triangle},
+        Scalar,
+    },
+};
+
+/// Why a domain couldn't be refined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefinementError {
+    /// The segments couldn't be triangulated.
+    Constraint(ConstraintError),
+    /// The refinement added this many points without finishing.
+    TooManyPoints(usize),
+}
+
+impl fmt::Display for RefinementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constraint(error) => error.fmt(f),
+            Self::TooManyPoints(n) => write!(f, "gave up after adding {n} points"),
+        }
+    }
+}
+
+impl std::error::Error for RefinementError {}
+
+impl From<ConstraintError> for RefinementError {
+    fn from(error: ConstraintError) -> Self {
+        Self::Constraint(error)
+    }
+}
+
+/// How many points a refinement may add for each input vertex, and for each triangle
+/// that `max_area` asks for, before giving up.
+const POINTS_PER_VERTEX: usize = 1000;
+
+// https://en.wikipedia.org/wiki/Delaunay_refinement#Ruppert's_algorithm
+//
+// Meshes the domain enclosed by `segments` with triangles whose angles are all at least
+// `min_angle` (in radians) and, if given, whose areas are at most `max_area`. Starting
+// from the constrained Delaunay triangulation of the points and segments, the
+// circumcenters of triangles that are too skinny or too large are added, and segments
+// are split in half whenever a vertex (or a circumcenter about to be added) lies inside
+// their diametral circle. The segments are kept as edges of the mesh, and triangles
+// outside of them are dropped, so they have to enclose a region to mesh.
+//
+// In exact arithmetic this finishes for minimum angles up to about 20.7 degrees.
+// Segments that meet at less than 60 degrees are split on concentric circles around
+// the vertex they share, which keeps them from splitting each other forever, and the
+// triangles caught between such segments are left as they are: an angle of the domain
+// itself can't be improved upon. Rounded split points don't lie exactly on their
+// segments though, and segments that (nearly) overlap can keep encroaching upon each
+// other. So the refinement gives up with `TooManyPoints` once it has added a thousand
+// points for every input vertex and every triangle `max_area` calls for.
+pub fn ruppert<T: Scalar>(
+    points: &[Point<T>],
+    segments: &[LineSegment<T>],
+    min_angle: f64,
+    max_area: Option<T>,
+) -> Result<Vec<Triangle<T>>, RefinementError> {
+    let mut mesh = Triangulation::new(points, segments)?;
+    mesh.remove_exterior();
+    let area = mesh
+        .triangles()
+        .iter()
+        .map(|t| t.volume().to_f64())
+        .sum::<f64>();
+    let sized = max_area.map_or(0.0, |max_area| area / max_area.to_f64());
+    let limit = POINTS_PER_VERTEX.saturating_mul(mesh.points.len().saturating_add(sized as usize));
+    let mut refinement = Refinement {
+        inputs: mesh.points.len(),
+        mesh,
+        segments,
+        on_segment: HashMap::new(),
+        encroached: vec![],
+        triangles: vec![],
+    };
+    for t in 0..refinement.mesh.triangles.len() {
+        refinement.enqueue(t);
+    }
+
+    loop {
+        let added = refinement.mesh.points.len() - refinement.inputs;
+        if added > limit {
+            return Err(RefinementError::TooManyPoints(added));
+        }
+
+        // Encroached segments come first, as circumcenters are only added once no
+        // segment is encroached.
+        if let Some((a, b)) = refinement.encroached.pop() {
+            if refinement.mesh.constraints.contains_key(&(a, b)) {
+                refinement.split(a, b);
+            }
+            continue;
+        }
+        let Some(t) = refinement.triangles.pop() else {
+            break;
+        };
+        let Some(vertices) = refinement.mesh.triangles[t] else {
+            continue;
+        };
+        let triangle = Triangle {
+            vertices: vertices.map(|v| refinement.mesh.points[v]),
+        };
+        let bad = triangle.min_angle() < min_angle
+            || max_area.is_some_and(|max_area| triangle.volume() > max_area);
+        if !bad || refinement.in_small_angle(vertices) {
+            continue;
+        }
+        let Some(circle) = triangle.circumscribe() else {
+            continue;
+        };
+        let center = Point {
+            x: circle.center.x,
+            y: circle.center.y,
+        };
+
+        // Rather than adding a circumcenter that encroaches upon segments, split those
+        // segments and try the triangle again afterwards. A segment too short to be
+        // split any further means giving up on the triangle.
+        let encroached = refinement
+            .mesh
+            .constraints
+            .keys()
+            .filter(|&&(a, b)| {
+                let [a, b] = [a, b].map(|v| refinement.mesh.points[v]);
+                encroaches(a, b, center)
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        if !encroached.is_empty() {
+            if encroached
+                .iter()
+                .all(|&(a, b)| refinement.split_point(a, b).is_some())
+            {
+                refinement.encroached.extend(encroached);
+                refinement.triangles.push(t);
+            }
+            continue;
+        }
+        let Some(start) = refinement.mesh.locate(t, center) else {
+            continue;
+        };
+        let corners = refinement.mesh.triangles[start].unwrap();
+        if corners.iter().any(|&v| refinement.mesh.points[v] == center) {
+            continue;
+        }
+        let (_, new) = refinement.mesh.insert_point(center, start);
+        for t in new {
+            refinement.enqueue(t);
+        }
+    }
+
+    Ok(refinement.mesh.triangles())
+}
+
+/// The state of a refinement in progress.
+struct Refinement<'a, T: Scalar> {
+    mesh: Triangulation<T>,
+    /// The number of vertices the mesh started out with.
+    inputs: usize,
+    segments: &'a [LineSegment<T>],
+    /// The segment each vertex added by splitting a segment lies on.
+    on_segment: HashMap<usize, usize>,
+    /// Segment edges that might be encroached upon, with the lower vertex first.
+    encroached: Vec<(usize, usize)>,
+    /// Triangles that might be skinny or too large.
+    triangles: Vec<usize>,
+}
+
+impl<T: Scalar> Refinement<'_, T> {
+    /// Queues up a triangle that was just added, along with its segment edges when its
+    /// third vertex encroaches upon them.
+    fn enqueue(&mut self, t: usize) {
+        let Some([a, b, c]) = self.mesh.triangles[t] else {
+            return;
+        };
+        self.triangles.push(t);
+        for [u, v, w] in [[a, b, c], [b, c, a], [c, a, b]] {
+            let key = (u.min(v), u.max(v));
+            let [u, v, w] = [u, v, w].map(|v| self.mesh.points[v]);
+            if self.mesh.constraints.contains_key(&key) && encroaches(u, v, w) {
+                self.encroached.push(key);
+            }
+        }
+    }
+
+    /// Where to split the segment edge from a to b, if it isn't too short to split.
+    /// Rounding may put the point off the edge, but never past one of its ends.
+    ///
+    /// An edge with one end at an input vertex is split at a power of two distance from
+    /// that vertex, so that the edges of segments meeting there at a small angle are
+    /// all split at the same distances, and stop encroaching upon each other.
+    fn split_point(&self, a: usize, b: usize) -> Option<Point<T>> {
+        let [pa, pb] = [a, b].map(|v| self.mesh.points[v]);
+        let length = (pb - pa).x.to_f64().hypot((pb - pa).y.to_f64());
+        let shell = |length: f64| 2f64.powf((length / 2.0).log2().round()) / length;
+        let t = match (a < self.inputs, b < self.inputs) {
+            (true, false) => shell(length),
+            (false, true) => 1.0 - shell(length),
+            _ => 0.5,
+        };
+        let t = T::from_f64(t);
+        let p = Point {
+            x: pa.x + (pb.x - pa.x) * t,
+            y: pa.y + (pb.y - pa.y) * t,
+        };
+        let along = |u: Point<T>, v: Point<T>| {
+            let (d, e) = (v - u, pb - pa);
+            d.x * e.x + d.y * e.y > T::zero()
+        };
+        (along(pa, p) && along(p, pb)).then_some(p)
+    }
+
+    fn split(&mut self, a: usize, b: usize) {
+        let Some(p) = self.split_point(a, b) else {
+            return;
+        };
+        let segment = self.mesh.constraints[&(a, b)];
+        let (p, new) = self.mesh.split(a, b, p);
+        self.on_segment.insert(p, segment);
+        for t in new {
+            self.enqueue(t);
+        }
+    }
+
+    /// Whether the shortest edge of the triangle runs between two segments that meet
+    /// at one of their ends, so that the triangle is cut off by a small angle of the
+    /// domain.
+    fn in_small_angle(&self, vertices: [usize; 3]) -> bool {
+        let [a, b, c] = vertices;
+        let length = |u: usize, v: usize| {
+            let d = self.mesh.points[v] - self.mesh.points[u];
+            d.x * d.x + d.y * d.y
+        };
+        let (u, v) = [(a, b), (b, c), (c, a)]
+            .into_iter()
+            .min_by(|&(a, b), &(c, d)| length(a, b).total_cmp(&length(c, d)))
+            .unwrap();
+        let (Some(&s), Some(&t)) = (self.on_segment.get(&u), self.on_segment.get(&v)) else {
+            return false;
+        };
+        let [s, t] = [s, t].map(|i| self.segments[i].vertices);
+        s != t && s.iter().any(|p| t.contains(p))
+    }
+}
+
+/// Whether p lies strictly inside the circle with the segment from a to b as diameter.
+fn encroaches<T: Scalar>(a: Point<T>, b: Point<T>, p: Point<T>) -> bool {
+    let (u, v) = (a - p, b - p);
+    u.x * v.x + u.y * v.y < T::zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    /// The closed polygon through `vertices`, as segments.
+    fn polygon(vertices: &[Point<f64>]) -> Vec<LineSegment<f64>> {
+        (0..vertices.len())
+            .map(|i| LineSegment {
+                vertices: [vertices[i], vertices[(i + 1) % vertices.len()]],
+            })
+            .collect()
+    }
+
+    /// Checks that the triangles are counter-clockwise, cover `area` in total, and have
+    /// edges all along the segments, which have to be on the boundary of the domain.
+    fn check(triangles: &[Triangle<f64>], segments: &[LineSegment<f64>], area: f64) {
+        let length = |p: Point<f64>| p.x.hypot(p.y);
+        let mut total = 0.0;
+        let mut along = vec![0.0; segments.len()];
+        for t in triangles {
+            let [a, b, c] = t.vertices;
+            assert!(f64::orient2d(a.into(), b.into(), c.into()).is_gt());
+            total += t.volume();
+            for [p, q] in [[a, b], [b, c], [c, a]] {
+                for (s, along) in segments.iter().zip(&mut along) {
+                    // Split points are rounded, so they're only close to the segment.
+                    let [u, v] = s.vertices;
+                    let on = |r: Point<f64>| {
+                        let (d, e) = (r - u, v - u);
+                        let t = (d.x * e.x + d.y * e.y) / length(e).powi(2);
+                        (d.x * e.y - d.y * e.x).abs() < 1e-12 && (-1e-12..=1.0 + 1e-12).contains(&t)
+                    };
+                    if on(p) && on(q) {
+                        *along += length(q - p);
+                    }
+                }
+            }
+        }
+        assert!(
+            (total - area).abs() < 1e-9,
+            "area {total} instead of {area}"
+        );
+        for (s, along) in segments.iter().zip(along) {
+            let [u, v] = s.vertices;
+            assert!((along - length(v - u)).abs() < 1e-9, "{s:?} is not covered");
+        }
+    }
+
+    #[test]
+    fn test_square() {
+        let square = [
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(1.0, 1.0),
+            point(0.0, 1.0),
+        ];
+        let segments = polygon(&square);
+        let min_angle = 20f64.to_radians();
+        let triangles = ruppert(&[], &segments, min_angle, None).unwrap();
+        check(&triangles, &segments, 1.0);
+        assert!(triangles.iter().all(|t| t.min_angle() >= min_angle));
+
+        let triangles = ruppert(&[], &segments, min_angle, Some(0.01)).unwrap();
+        check(&triangles, &segments, 1.0);
+        assert!(triangles.len() >= 100);
+        assert!(triangles.iter().all(|t| t.volume() <= 0.01));
+        assert!(triangles.iter().all(|t| t.min_angle() >= min_angle));
+    }
+
+    #[test]
+    fn test_l_shape() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let outline = [
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 1.0),
+            point(1.0, 1.0),
+            point(1.0, 2.0),
+            point(0.0, 2.0),
+        ];
+        let segments = polygon(&outline);
+        // Points in the notch are carved away along with it.
+        let points = (0..50)
+            .map(|_| point(rng.gen_range(0.0..2.0), rng.gen_range(0.0..2.0)))
+            .collect::<Vec<_>>();
+        let min_angle = 20f64.to_radians();
+        let triangles = ruppert(&points, &segments, min_angle, None).unwrap();
+        check(&triangles, &segments, 3.0);
+        assert!(triangles.iter().all(|t| t.min_angle() >= min_angle));
+    }
+
+    #[test]
+    fn test_small_angle() {
+        // The domain has an angle of 5 degrees, which can't be improved upon, but the
+        // refinement still finishes.
+        let angle = 5f64.to_radians();
+        let wedge = [
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(angle.cos(), angle.sin()),
+        ];
+        let segments = polygon(&wedge);
+        let triangles = ruppert(&[], &segments, 20f64.to_radians(), None).unwrap();
+        check(&triangles, &segments, angle.sin() / 2.0);
+    }
+
+    #[test]
+    fn test_sliver() {
+        // Exactly, b doesn't lie on the line from a to c, but it's as close to it as
+        // rounding allows, and so are the points splitting either segment to the other.
+        let (a, b, c) = ((-1.0, -1.0 / 3.0), (-2.0 / 3.0, 0.0), (1.0 / 3.0, 1.0));
+        let triangle = polygon(&[a, b, c].map(|(x, y)| point(x, y)));
+        let min_angle = 20f64.to_radians();
+        let error = ruppert(&[], &triangle, min_angle, None).unwrap_err();
+        assert!(matches!(error, RefinementError::TooManyPoints(_)));
+
+        let triangle = triangle
+            .iter()
+            .map(|s| LineSegment {
+                vertices: s.vertices.map(|p| Point {
+                    x: p.x as f32,
+                    y: p.y as f32,
+                }),
+            })
+            .collect::<Vec<_>>();
+        let error = ruppert(&[], &triangle, min_angle, None).unwrap_err();
+        assert!(matches!(error, RefinementError::TooManyPoints(_)));
+    }
+
+    #[test]
+    fn test_intersecting() {
+        let segments = [
+            LineSegment {
+                vertices: [point(0.0, 0.0), point(1.0, 1.0)],
+            },
+            LineSegment {
+                vertices: [point(1.0, 0.0), point(0.0, 1.0)],
+            },
+        ];
+        let error = ruppert(&[], &segments, 0.3, None).unwrap_err();
+        assert_eq!(
+            error,
+            RefinementError::Constraint(ConstraintError::Intersecting(0, 1))
+        );
+    }
+}
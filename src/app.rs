@@ -1,13 +1,15 @@
+use std::time::{Duration, Instant};
+
 use eframe::egui;
 
-use crate::{
-    algorithms,
+use compgeo::{
+    algorithms::{self, BooleanOperation, JarvisStep, SweepStep},
     data::{
         point::Point2,
         pos2,
-        simplex::{Point, Simplex, Triangle},
-        sphere::{Sphere, Sphere1},
-        Dot, Pos2,
+        simplex::{LineSegment, Point, Simplex, Triangle},
+        sphere::Sphere1,
+        Dot, Polygon, Pos2, Voronoi,
     },
 };
 
@@ -21,18 +23,239 @@ const DEFAULT_RADIUS: f32 = 1.00;
 const MAX_ZOOM: f32 = 1.0;
 // const DEFAULT_ZOOM: f32 = 0.45;
 const DEFAULT_ZOOM: f32 = 1.0;
+// quality meshes
+const MIN_ANGLE_DEGREES: f64 = 20.0;
+// canvas
+const CANVAS_MIN: Point = Point { x: -1.0, y: -1.0 };
+const CANVAS_MAX: Point = Point { x: 1.0, y: 1.0 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Algorithm {
+    BentleyOttmann,
+    BowyerWatson,
+    Chan,
+    ConfigurationSpace,
+    DelaunayDual,
     DelaunayTriangulation,
+    EarClipping,
+    Fortune,
     GrahamScan,
+    JarvisMarch,
+    MonotoneChain,
+    MonotoneTriangulation,
+    PolygonBoolean(BooleanOperation),
+    Quickhull,
+    Ruppert,
 }
 
-impl Into<&str> for Algorithm {
-    fn into(self) -> &'static str {
+impl Algorithm {
+    pub const ALL: [Algorithm; 18] = [
+        Algorithm::GrahamScan,
+        Algorithm::JarvisMarch,
+        Algorithm::MonotoneChain,
+        Algorithm::Quickhull,
+        Algorithm::Chan,
+        Algorithm::DelaunayTriangulation,
+        Algorithm::BowyerWatson,
+        Algorithm::Ruppert,
+        Algorithm::Fortune,
+        Algorithm::DelaunayDual,
+        Algorithm::BentleyOttmann,
+        Algorithm::EarClipping,
+        Algorithm::MonotoneTriangulation,
+        Algorithm::PolygonBoolean(BooleanOperation::Union),
+        Algorithm::PolygonBoolean(BooleanOperation::Intersection),
+        Algorithm::PolygonBoolean(BooleanOperation::Difference),
+        Algorithm::PolygonBoolean(BooleanOperation::Xor),
+        Algorithm::ConfigurationSpace,
+    ];
+
+    /// How many polygons drawn with the mouse the algorithm runs over, rather than the
+    /// random points.
+    fn num_polygons(self) -> usize {
         match self {
-            Self::DelaunayTriangulation => "Delaunay Triangulation",
-            Self::GrahamScan => "Graham Scan",
+            Self::EarClipping | Self::MonotoneTriangulation => 1,
+            Self::PolygonBoolean(_) | Self::ConfigurationSpace => 2,
+            _ => 0,
+        }
+    }
+
+    /// Runs the algorithm over `points`, producing everything it wants drawn. Algorithms
+    /// running over two polygons get the second one as `other`.
+    fn run(self, points: &[Pos2], other: &[Pos2], keep_collinear: bool) -> Output {
+        match self {
+            Self::GrahamScan => Output {
+                vertices: algorithms::graham_scan(points),
+                ..Default::default()
+            },
+            Self::JarvisMarch => {
+                let (vertices, steps) = algorithms::jarvis_march_steps(points);
+                Output {
+                    vertices,
+                    steps,
+                    ..Default::default()
+                }
+            }
+            Self::MonotoneChain => Output {
+                vertices: algorithms::monotone_chain(points, keep_collinear),
+                ..Default::default()
+            },
+            Self::Chan => Output {
+                vertices: algorithms::chan(points),
+                ..Default::default()
+            },
+            Self::Quickhull => Output {
+                vertices: algorithms::quickhull(points),
+                ..Default::default()
+            },
+            Self::Fortune => {
+                let points = points.iter().map(|p| (*p).into()).collect::<Vec<Point>>();
+                Output::voronoi(&algorithms::fortune(&points))
+            }
+            Self::DelaunayDual => {
+                let points = points.iter().map(|p| (*p).into()).collect::<Vec<Point>>();
+                let triangles = algorithms::bowyer_watson(&points);
+                Output::voronoi(&algorithms::delaunay_dual(&points, &triangles))
+            }
+            Self::DelaunayTriangulation | Self::BowyerWatson => {
+                let points = points.iter().map(|p| (*p).into()).collect::<Vec<Point>>();
+                let triangles = if self == Self::DelaunayTriangulation {
+                    algorithms::dewall(&points)
+                } else {
+                    algorithms::bowyer_watson(&points)
+                };
+                let spheres = triangles.iter().filter_map(|t| t.circumscribe()).collect();
+                Output {
+                    triangles,
+                    spheres,
+                    ..Default::default()
+                }
+            }
+            Self::BentleyOttmann => {
+                // Every two points make up a segment.
+                let segments = points
+                    .chunks_exact(2)
+                    .map(|pair| LineSegment {
+                        vertices: [pair[0].into(), pair[1].into()],
+                    })
+                    .collect::<Vec<_>>();
+                let (intersections, sweep) = algorithms::bentley_ottmann_steps(&segments);
+                Output {
+                    edges: segments
+                        .iter()
+                        .map(|s| s.vertices.map(|p| pos2(p.x, p.y)))
+                        .collect(),
+                    intersections: intersections
+                        .iter()
+                        .map(|i| pos2(i.point.x, i.point.y))
+                        .collect(),
+                    sweep,
+                    ..Default::default()
+                }
+            }
+            Self::Ruppert => {
+                // Mesh the convex hull of the points, keeping all of them as vertices.
+                let hull = algorithms::graham_scan(points)
+                    .into_iter()
+                    .map(Point::from)
+                    .collect::<Vec<_>>();
+                let segments = (0..hull.len())
+                    .map(|i| LineSegment {
+                        vertices: [hull[i], hull[(i + 1) % hull.len()]],
+                    })
+                    .collect::<Vec<_>>();
+                let points = points.iter().map(|p| (*p).into()).collect::<Vec<Point>>();
+                // Points crowded together more closely than rounding can resolve may
+                // keep the refinement from finishing, and then the mesh is shown
+                // unrefined. That can't fail: the edges of a convex hull never cross.
+                let min_angle = MIN_ANGLE_DEGREES.to_radians();
+                let triangles = algorithms::ruppert(&points, &segments, min_angle, None)
+                    .or_else(|_| algorithms::constrained_delaunay(&points, &segments))
+                    .unwrap();
+                let qualities = triangles
+                    .iter()
+                    .map(|t| (t.min_angle() / std::f64::consts::FRAC_PI_3) as f32)
+                    .collect();
+                Output {
+                    triangles,
+                    qualities,
+                    ..Default::default()
+                }
+            }
+            Self::EarClipping => {
+                let polygon = Polygon::new(points.iter().map(|p| (*p).into()).collect());
+                Output {
+                    vertices: points.to_vec(),
+                    ears: algorithms::ear_clipping(&polygon),
+                    ..Default::default()
+                }
+            }
+            Self::MonotoneTriangulation => {
+                let polygon = Polygon::new(points.iter().map(|p| (*p).into()).collect());
+                let (triangles, diagonals) = algorithms::monotone_triangulation(&polygon);
+                Output {
+                    vertices: points.to_vec(),
+                    triangles,
+                    diagonals: diagonals
+                        .iter()
+                        .map(|d| d.vertices.map(|p| pos2(p.x, p.y)))
+                        .collect(),
+                    ..Default::default()
+                }
+            }
+            Self::PolygonBoolean(operation) => {
+                let [a, b] = [points, other]
+                    .map(|ring| Polygon::new(ring.iter().map(|p| (*p).into()).collect()));
+                let result = algorithms::polygon_boolean(&a, &b, operation);
+                Output {
+                    vertices: points.to_vec(),
+                    edges: outlines(&result),
+                    regions: result.iter().flat_map(algorithms::ear_clipping).collect(),
+                    ..Default::default()
+                }
+            }
+            Self::ConfigurationSpace => {
+                // The first polygon is an obstacle, and the second a robot which moves
+                // around by its first vertex. The robot hits the obstacle wherever that
+                // vertex is in the obstacle grown by the robot turned around it.
+                let obstacle = Polygon::new(points.iter().map(|p| (*p).into()).collect());
+                let robot = other.first().map_or(vec![], |reference| {
+                    let reference = Point::from(*reference);
+                    other.iter().map(|p| reference - Point::from(*p)).collect()
+                });
+                let result = algorithms::minkowski_sum(&obstacle, &Polygon::new(robot));
+                Output {
+                    vertices: points.to_vec(),
+                    edges: outlines(&result),
+                    regions: result.iter().flat_map(algorithms::ear_clipping).collect(),
+                    ..Default::default()
+                }
+            }
+        }
+    }
+}
+
+impl From<Algorithm> for &str {
+    fn from(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::BentleyOttmann => "Segment Intersections (Bentley-Ottmann)",
+            Algorithm::BowyerWatson => "Delaunay Triangulation (Bowyer-Watson)",
+            Algorithm::Chan => "Chan's Algorithm",
+            Algorithm::ConfigurationSpace => "Configuration Space (Minkowski Sum)",
+            Algorithm::DelaunayDual => "Voronoi Diagram (Delaunay dual)",
+            Algorithm::DelaunayTriangulation => "Delaunay Triangulation (DeWall)",
+            Algorithm::EarClipping => "Polygon Triangulation (Ear Clipping)",
+            Algorithm::Fortune => "Voronoi Diagram (Fortune)",
+            Algorithm::GrahamScan => "Graham Scan",
+            Algorithm::JarvisMarch => "Jarvis March",
+            Algorithm::MonotoneChain => "Monotone Chain",
+            Algorithm::MonotoneTriangulation => "Polygon Triangulation (Monotone)",
+            Algorithm::PolygonBoolean(BooleanOperation::Union) => "Polygon Union",
+            Algorithm::PolygonBoolean(BooleanOperation::Intersection) => "Polygon Intersection",
+            Algorithm::PolygonBoolean(BooleanOperation::Difference) => "Polygon Difference",
+            Algorithm::PolygonBoolean(BooleanOperation::Xor) => "Polygon Xor",
+            Algorithm::Quickhull => "Quickhull",
+            Algorithm::Ruppert => "Quality Mesh (Ruppert)",
         }
     }
 }
@@ -52,15 +275,82 @@ impl eframe::App for EguiApp {
     }
 }
 
+/// The geometry produced by a single run of an [`Algorithm`].
+#[derive(Default, PartialEq)]
+pub struct Output {
+    /// Vertices of a closed polygon, e.g. a convex hull.
+    vertices: Vec<Pos2>,
+    spheres: Vec<Sphere1>,
+    triangles: Vec<Triangle>,
+    /// The quality of each triangle, as its smallest angle over 60 degrees: 1 for an
+    /// equilateral triangle, down to 0 for a degenerate one. Triangles with a quality
+    /// are filled with a color going from red to green.
+    qualities: Vec<f32>,
+    /// The steps taken by Jarvis march to wrap `vertices`.
+    steps: Vec<JarvisStep>,
+    /// Line segments, e.g. the edges of a Voronoi diagram.
+    edges: Vec<[Pos2; 2]>,
+    /// The Voronoi cell of each point, if any.
+    cells: Vec<Vec<Pos2>>,
+    /// Points where `edges` meet.
+    intersections: Vec<Pos2>,
+    /// The events handled by the sweep finding `intersections`.
+    sweep: Vec<SweepStep>,
+    /// The ears clipped off a polygon, in order, one more of which is cut per step.
+    ears: Vec<Triangle>,
+    /// Diagonals splitting a polygon into pieces, drawn over `triangles`.
+    diagonals: Vec<[Pos2; 2]>,
+    /// Triangles covering a region, e.g. the result of combining polygons, filled in.
+    regions: Vec<Triangle>,
+}
+
+impl Output {
+    /// The number of steps that can be stepped through.
+    fn num_steps(&self) -> usize {
+        self.steps.len().max(self.sweep.len()).max(self.ears.len())
+    }
+
+    /// The parts of a Voronoi diagram that lie on the canvas.
+    fn voronoi(voronoi: &Voronoi) -> Self {
+        let to_pos2 = |p: Point| pos2(p.x, p.y);
+        Self {
+            edges: voronoi
+                .edges
+                .iter()
+                .filter_map(|edge| voronoi.clip_edge(edge, CANVAS_MIN, CANVAS_MAX))
+                .map(|edge| edge.map(to_pos2))
+                .collect(),
+            cells: voronoi
+                .cells(CANVAS_MIN, CANVAS_MAX)
+                .into_iter()
+                .map(|cell| cell.into_iter().map(to_pos2).collect())
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(PartialEq)]
 pub struct AppState {
     zoom: f32,
     radius: f32,
     num_points: usize,
     points: Vec<Dot>,
-    vertices: Vec<Pos2>,
-    spheres: Vec<Sphere1>,
-    triangles: Vec<Triangle>,
+    /// The vertices of the polygon drawn by clicking on the canvas, in order.
+    polygon: Vec<Pos2>,
+    /// The vertices of a second polygon, drawn by right-clicking on the canvas.
+    other_polygon: Vec<Pos2>,
+    output: Output,
+    /// How many of the output's steps to show, showing the final output once they're all
+    /// taken.
+    step: usize,
+    /// How long the algorithm took to produce `output`.
+    elapsed: Duration,
+    show_circumcircles: bool,
+    /// Whether Voronoi cells are filled with the color of their point.
+    fill_cells: bool,
+    /// Whether hulls keep the points lying on their edges.
+    keep_collinear: bool,
     rendered: bool,
     algorithm: Algorithm,
 }
@@ -72,9 +362,14 @@ impl Default for AppState {
             radius: DEFAULT_RADIUS,
             num_points: DEFAULT_NUM_POINTS,
             points: vec![],
-            vertices: vec![],
-            spheres: vec![],
-            triangles: vec![],
+            polygon: vec![],
+            other_polygon: vec![],
+            output: Output::default(),
+            step: 0,
+            elapsed: Duration::ZERO,
+            show_circumcircles: false,
+            fill_cells: false,
+            keep_collinear: false,
             rendered: false,
             algorithm: Algorithm::GrahamScan,
         }
@@ -116,17 +411,34 @@ impl App {
         ctx.input(|input| {
             if input.key_pressed(egui::Key::Space) {
                 self.state.points = vec![];
+                self.state.polygon = vec![];
+                self.state.other_polygon = vec![];
                 self.state.rendered = false;
             }
         });
 
+        let num_polygons = self.state.algorithm.num_polygons();
+        if num_polygons > 0 {
+            // Windows are on top of the canvas, so clicks on them don't end up here.
+            let canvas = ui.interact(ui.max_rect(), ui.id().with("canvas"), egui::Sense::click());
+            let position = canvas.interact_pointer_pos();
+            if let Some(p) = position.and_then(|pos| self.to_graph_space(pos)) {
+                if canvas.clicked() {
+                    self.state.polygon.push(p);
+                    self.state.rendered = false;
+                } else if canvas.secondary_clicked() && num_polygons > 1 {
+                    self.state.other_polygon.push(p);
+                    self.state.rendered = false;
+                }
+            }
+        }
+
         if self.state.points.len() < self.state.num_points {
             let num_to_generate = self.state.num_points - self.state.points.len();
             let mut rng = rand::thread_rng();
-            let mut points = vec![Dot::default(); num_to_generate];
-            for i in 0..num_to_generate {
-                points[i] = Dot::random(&mut rng);
-            }
+            let mut points = (0..num_to_generate)
+                .map(|_| Dot::random(&mut rng))
+                .collect();
             self.state.points.append(&mut points);
             self.state.rendered = false;
         }
@@ -137,9 +449,21 @@ impl App {
         }
 
         if !self.state.rendered {
-            self.state.vertices = algorithms::graham_scan(
-                &self.state.points.iter().map(|p| p.pos).collect::<Vec<_>>(),
+            // Replace (rather than extend) the output, so nothing drawn by the previously
+            // selected algorithm sticks around.
+            let points = if self.state.algorithm.num_polygons() > 0 {
+                self.state.polygon.clone()
+            } else {
+                self.state.points.iter().map(|p| p.pos).collect()
+            };
+            let start = Instant::now();
+            self.state.output = self.state.algorithm.run(
+                &points,
+                &self.state.other_polygon,
+                self.state.keep_collinear,
             );
+            self.state.elapsed = start.elapsed();
+            self.state.step = self.state.output.num_steps();
             self.state.rendered = true;
         }
 
@@ -152,8 +476,8 @@ impl App {
         //         vertices: [a, b, c],
         //     };
         //     let c = t.circumscribe().unwrap();
-        //     self.state.triangles = vec![t];
-        //     self.state.spheres = vec![c];
+        //     self.state.output.triangles = vec![t];
+        //     self.state.output.spheres = vec![c];
         //     self.state.rendered = true;
         // }
 
@@ -189,25 +513,38 @@ impl App {
                         );
                         ui.end_row();
                         egui::ComboBox::from_id_salt("algorithm-selection")
-                            .selected_text(format!(
-                                "{}",
-                                <Algorithm as Into<&str>>::into(self.state.algorithm)
-                            ))
+                            .selected_text(<Algorithm as Into<&str>>::into(self.state.algorithm))
                             .show_ui(ui, |ui| {
-                                ui.selectable_value(
-                                    &mut self.state.algorithm,
-                                    Algorithm::GrahamScan,
-                                    <Algorithm as Into<&str>>::into(Algorithm::GrahamScan),
-                                );
-                                ui.selectable_value(
-                                    &mut self.state.algorithm,
-                                    Algorithm::DelaunayTriangulation,
-                                    <Algorithm as Into<&str>>::into(
-                                        Algorithm::DelaunayTriangulation,
-                                    ),
-                                );
+                                for algorithm in Algorithm::ALL {
+                                    let response = ui.selectable_value(
+                                        &mut self.state.algorithm,
+                                        algorithm,
+                                        <Algorithm as Into<&str>>::into(algorithm),
+                                    );
+                                    if response.changed() {
+                                        self.state.rendered = false;
+                                    }
+                                }
                             });
                         ui.end_row();
+                        ui.checkbox(&mut self.state.show_circumcircles, "circumcircles");
+                        ui.end_row();
+                        ui.checkbox(&mut self.state.fill_cells, "fill cells");
+                        ui.end_row();
+                        if ui
+                            .checkbox(&mut self.state.keep_collinear, "collinear hull points")
+                            .changed()
+                        {
+                            self.state.rendered = false;
+                        }
+                        ui.end_row();
+                        let steps = self.state.output.num_steps();
+                        if steps > 0 {
+                            ui.add(egui::Slider::new(&mut self.state.step, 0..=steps).text("step"));
+                            ui.end_row();
+                        }
+                        ui.label(format!("took {:.2?}", self.state.elapsed));
+                        ui.end_row();
                     });
                 egui::reset_button(ui, &mut self.state, "Reset");
             });
@@ -216,25 +553,108 @@ impl App {
     fn paint(&mut self) {
         let mut shapes: Vec<egui::Shape> = Vec::new();
 
-        shapes.append(
-            &mut self
-                .state
-                .points
+        if self.state.fill_cells {
+            shapes.extend(
+                self.state
+                    .output
+                    .cells
+                    .iter()
+                    .zip(&self.state.points)
+                    .filter(|(cell, _)| !cell.is_empty())
+                    .map(|(cell, Dot { color, .. })| {
+                        let cell = cell.iter().map(|p| Point::from(*p)).collect::<Vec<_>>();
+                        self.fill_polygon(&cell, *color, egui::Stroke::NONE)
+                    }),
+            );
+        }
+
+        shapes.extend(
+            self.state
+                .output
+                .triangles
                 .iter()
-                .map(|Dot { pos, color }| {
-                    egui::Shape::circle_filled(
-                        self.to_screen_space(*pos),
-                        self.state.radius,
-                        *color,
-                    )
-                })
-                .collect(),
+                .zip(&self.state.output.qualities)
+                .map(|(t, quality)| {
+                    let quality = quality.clamp(0.0, 1.0);
+                    let color = egui::Color32::from_rgb(
+                        ((1.0 - quality) * 200.0) as u8,
+                        (quality * 200.0) as u8,
+                        60,
+                    );
+                    self.fill_polygon(&t.vertices, color, egui::Stroke::NONE)
+                }),
         );
 
+        // The ears clipped so far are filled in, and the one clipped next is outlined.
+        let clipped = self.state.step.min(self.state.output.ears.len());
+        shapes.extend(self.state.output.ears[..clipped].iter().map(|t| {
+            self.fill_polygon(
+                &t.vertices,
+                egui::Color32::DARK_GREEN,
+                (1.0, egui::Color32::LIGHT_GRAY),
+            )
+        }));
+        if let Some(ear) = self.state.output.ears.get(self.state.step) {
+            shapes.extend(self.draw_triangle(ear, 2.0, egui::Color32::YELLOW));
+        }
+
+        shapes.extend(self.state.output.regions.iter().map(|t| {
+            let color = egui::Color32::from_rgba_unmultiplied(60, 160, 60, 120);
+            self.fill_polygon(&t.vertices, color, egui::Stroke::NONE)
+        }));
+
+        let num_polygons = self.state.algorithm.num_polygons();
+        if num_polygons > 0 {
+            shapes.extend(self.state.polygon.iter().map(|p| {
+                egui::Shape::circle_filled(
+                    self.to_screen_space(*p),
+                    self.state.radius + 1.0,
+                    egui::Color32::LIGHT_BLUE,
+                )
+            }));
+        }
+        if num_polygons > 1 {
+            // The first polygon is drawn as the output's `vertices`.
+            let other = &self.state.other_polygon;
+            shapes.extend((0..other.len()).map(|i| {
+                let edge = [other[i], other[(i + 1) % other.len()]];
+                self.draw_line(edge, 1.0, egui::Color32::LIGHT_RED)
+            }));
+            shapes.extend(other.iter().map(|p| {
+                egui::Shape::circle_filled(
+                    self.to_screen_space(*p),
+                    self.state.radius + 1.0,
+                    egui::Color32::LIGHT_RED,
+                )
+            }));
+        }
+        if num_polygons == 0 {
+            shapes.append(
+                &mut self
+                    .state
+                    .points
+                    .iter()
+                    .map(|Dot { pos, color }| {
+                        egui::Shape::circle_filled(
+                            self.to_screen_space(*pos),
+                            self.state.radius,
+                            *color,
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        // While stepping through the output only the part of the hull found so far is
+        // drawn, along with the edges being compared.
+        let step = self.state.output.steps.get(self.state.step);
+        let vertices = match step {
+            Some(step) => &self.state.output.vertices[..step.hull_len],
+            None => &self.state.output.vertices[..],
+        };
+
         shapes.append(
-            &mut self
-                .state
-                .vertices
+            &mut vertices
                 .windows(2)
                 .map(|w| {
                     let a = w[0];
@@ -244,46 +664,99 @@ impl App {
                 .collect(),
         );
 
-        if !self.state.vertices.is_empty() {
-            shapes.push(self.draw_line(
-                [
-                    *self.state.vertices.first().unwrap(),
-                    *self.state.vertices.last().unwrap(),
-                ],
-                1.0,
-                egui::Color32::LIGHT_BLUE,
-            ));
+        match step {
+            Some(JarvisStep {
+                candidate, best, ..
+            }) => {
+                let current = *vertices.last().unwrap();
+                shapes.push(self.draw_line([current, *candidate], 1.0, egui::Color32::YELLOW));
+                shapes.push(self.draw_line([current, *best], 1.0, egui::Color32::LIGHT_RED));
+                shapes.push(egui::Shape::circle_filled(
+                    self.to_screen_space(*best),
+                    self.state.radius + 2.0,
+                    egui::Color32::LIGHT_RED,
+                ));
+            }
+            None if !vertices.is_empty() => {
+                shapes.push(self.draw_line(
+                    [*vertices.first().unwrap(), *vertices.last().unwrap()],
+                    1.0,
+                    egui::Color32::LIGHT_BLUE,
+                ));
+            }
+            None => {}
         }
 
+        shapes.extend(
+            self.state
+                .output
+                .edges
+                .iter()
+                .map(|edge| self.draw_line(*edge, 1.0, egui::Color32::LIGHT_GRAY)),
+        );
+
+        // While stepping through the sweep only the intersections it found so far are
+        // drawn, along with the sweep line.
+        let sweep = self.state.output.sweep.get(self.state.step);
+        let found = sweep.map_or(self.state.output.intersections.len(), |step| step.found);
+        if let Some(SweepStep { position, .. }) = sweep {
+            let line = [pos2(position.x, -1.0), pos2(position.x, 1.0)];
+            shapes.push(self.draw_line(line, 1.0, egui::Color32::YELLOW));
+        }
+        shapes.extend(self.state.output.intersections[..found].iter().map(|p| {
+            egui::Shape::circle_filled(
+                self.to_screen_space(*p),
+                self.state.radius + 2.0,
+                egui::Color32::LIGHT_RED,
+            )
+        }));
+
         shapes.append(
             &mut self
                 .state
+                .output
                 .triangles
                 .iter()
                 .flat_map(|t| self.draw_triangle(t, 1.0, egui::Color32::LIGHT_GRAY))
                 .collect(),
         );
-
-        shapes.append(
-            &mut self
-                .state
-                .spheres
+        shapes.extend(
+            self.state
+                .output
+                .diagonals
                 .iter()
-                .flat_map(|s| {
-                    let Point2 { x, y } = s.center;
-                    let center = Pos2 { x, y };
-                    [
-                        // render the center
-                        egui::Shape::circle_filled(
-                            self.to_screen_space(pos2(x, y)),
-                            2.0,
-                            egui::Color32::LIGHT_GREEN,
-                        ),
-                        self.draw_sphere(s.radius, center, 1.0, egui::Color32::LIGHT_GREEN),
-                    ]
-                })
-                .collect(),
+                .map(|d| self.draw_line(*d, 2.0, egui::Color32::GOLD)),
         );
+
+        if self.state.show_circumcircles {
+            shapes.append(
+                &mut self
+                    .state
+                    .output
+                    .spheres
+                    .iter()
+                    .flat_map(|s| {
+                        let Point2 { x, y } = s.center;
+                        let center = Pos2 { x, y };
+                        // The circumcenters of thin triangles can land far outside of the
+                        // canvas, with only part of their circle on it.
+                        let dot = if on_canvas(center) {
+                            egui::Shape::circle_filled(
+                                self.to_screen_space(center),
+                                2.0,
+                                egui::Color32::LIGHT_GREEN,
+                            )
+                        } else {
+                            egui::Shape::Noop
+                        };
+                        [
+                            dot,
+                            self.draw_sphere(s.radius, center, 1.0, egui::Color32::LIGHT_GREEN),
+                        ]
+                    })
+                    .collect(),
+            );
+        }
         // shapes.append(&mut self.draw_debug_grid());
 
         self.add_shapes(shapes);
@@ -293,7 +766,7 @@ impl App {
     where
         I: IntoIterator<Item = egui::Shape>,
     {
-        (&mut self.graph_painter.as_ref().unwrap()).extend(shapes)
+        self.graph_painter.as_ref().unwrap().extend(shapes)
     }
 
     #[allow(dead_code)]
@@ -355,12 +828,48 @@ impl App {
         ]
     }
 
+    /// Draws the part of the line on the canvas.
     fn draw_line(&self, points: [Pos2; 2], stroke: f32, color: egui::Color32) -> egui::Shape {
-        let line = [
-            self.to_screen_space(points[0]),
-            self.to_screen_space(points[1]),
+        let segment = LineSegment {
+            vertices: points.map(Point::from),
+        };
+        match algorithms::liang_barsky(&segment, CANVAS_MIN, CANVAS_MAX) {
+            Some(LineSegment { vertices }) => {
+                let line = vertices.map(|p| self.to_screen_space(pos2(p.x, p.y)));
+                egui::Shape::line_segment(line, (stroke, color))
+            }
+            None => egui::Shape::Noop,
+        }
+    }
+
+    /// Fills the part of the convex polygon on the canvas.
+    fn fill_polygon(
+        &self,
+        vertices: &[Point],
+        fill: egui::Color32,
+        stroke: impl Into<egui::epaint::PathStroke>,
+    ) -> egui::Shape {
+        let canvas = [
+            CANVAS_MIN,
+            Point {
+                x: CANVAS_MAX.x,
+                y: CANVAS_MIN.y,
+            },
+            CANVAS_MAX,
+            Point {
+                x: CANVAS_MIN.x,
+                y: CANVAS_MAX.y,
+            },
         ];
-        egui::Shape::line_segment(line, (stroke, color))
+        let clipped = algorithms::sutherland_hodgman(vertices, &canvas);
+        if clipped.len() < 3 {
+            return egui::Shape::Noop;
+        }
+        let points = clipped
+            .into_iter()
+            .map(|p| self.to_screen_space(pos2(p.x, p.y)))
+            .collect();
+        egui::Shape::convex_polygon(points, fill, stroke)
     }
 
     fn draw_sphere(
@@ -370,18 +879,106 @@ impl App {
         stroke: f32,
         color: egui::Color32,
     ) -> egui::Shape {
-        let transformed_center = self.to_screen_space(center);
-        let width = self.graph_painter.as_ref().unwrap().clip_rect().width();
-        let radius = width * radius * 0.5;
-        egui::Shape::circle_stroke(transformed_center, radius, (stroke, color))
+        // A circle missing the canvas, or going around all of it, has nothing to draw.
+        let nearest = pos2(
+            center.x.clamp(CANVAS_MIN.x, CANVAS_MAX.x),
+            center.y.clamp(CANVAS_MIN.y, CANVAS_MAX.y),
+        );
+        let farthest = pos2(
+            if center.x < 0.0 {
+                CANVAS_MAX.x
+            } else {
+                CANVAS_MIN.x
+            },
+            if center.y < 0.0 {
+                CANVAS_MAX.y
+            } else {
+                CANVAS_MIN.y
+            },
+        );
+        let distance = |p: Pos2| (p.x - center.x).hypot(p.y - center.y);
+        if distance(nearest) > radius || distance(farthest) < radius {
+            return egui::Shape::Noop;
+        }
+        // A circle lying on the canvas is drawn whole.
+        let inside = [
+            center.x - CANVAS_MIN.x,
+            CANVAS_MAX.x - center.x,
+            center.y - CANVAS_MIN.y,
+            CANVAS_MAX.y - center.y,
+        ];
+        if inside.iter().all(|d| *d >= radius) {
+            let transformed_center = self.to_screen_space(center);
+            let width = self.graph_painter.as_ref().unwrap().clip_rect().width();
+            let radius = width * radius * 0.5;
+            return egui::Shape::circle_stroke(transformed_center, radius, (stroke, color));
+        }
+
+        // Otherwise the arc crossing the canvas is drawn as a polyline clipped to it, so
+        // the circles around thin triangles, centered far off the canvas, are too. That
+        // takes f64, as their points are the difference of huge numbers.
+        use std::f64::consts::{PI, TAU};
+        const SEGMENTS: usize = 128;
+        let [cx, cy, radius] = [center.x, center.y, radius].map(f64::from);
+        let [min, max] = [CANVAS_MIN, CANVAS_MAX].map(|p| Point {
+            x: f64::from(p.x),
+            y: f64::from(p.y),
+        });
+        let (start, end) = if on_canvas(center) {
+            (0.0, TAU)
+        } else {
+            // Seen from outside, the canvas spans less than half a turn around the
+            // direction towards its middle.
+            let angle = |x: f64, y: f64| (y - cy).atan2(x - cx);
+            let towards = angle((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+            let corners = [
+                [min.x, min.y],
+                [max.x, min.y],
+                [max.x, max.y],
+                [min.x, max.y],
+            ]
+            .map(|[x, y]| (angle(x, y) - towards + PI).rem_euclid(TAU) - PI);
+            let first = corners.into_iter().fold(f64::INFINITY, f64::min);
+            let last = corners.into_iter().fold(f64::NEG_INFINITY, f64::max);
+            (towards + first, towards + last)
+        };
+        let at = |i: usize| {
+            let t = start + (end - start) * i as f64 / SEGMENTS as f64;
+            Point {
+                x: cx + radius * t.cos(),
+                y: cy + radius * t.sin(),
+            }
+        };
+        let arcs = (0..SEGMENTS)
+            .filter_map(|i| {
+                let chord = LineSegment {
+                    vertices: [at(i), at(i + 1)],
+                };
+                algorithms::liang_barsky(&chord, min, max)
+            })
+            .map(|LineSegment { vertices }| {
+                let line = vertices.map(|p| self.to_screen_space(pos2(p.x as f32, p.y as f32)));
+                egui::Shape::line_segment(line, (stroke, color))
+            })
+            .collect();
+        egui::Shape::Vec(arcs)
     }
 
-    /// Takes a point `p` and converts it to screen space.
+    /// Takes a point `p` and converts it to screen space. Points off the canvas end up
+    /// off the painter's clip rect, so geometry reaching past the canvas is clipped to
+    /// it first (see `draw_line` and `fill_polygon`).
     fn to_screen_space(&self, p: Pos2) -> egui::Pos2 {
-        debug_assert!(
-            p.x <= 1.0 && p.x >= -1.0 && p.y <= 1.0 && p.y >= -1.0,
-            "position invariant: {p:?}"
-        );
+        self.screen_transform().transform_pos(egui::pos2(p.x, -p.y))
+    }
+
+    /// Takes a position on the screen and converts it back, if it lies on the canvas.
+    fn to_graph_space(&self, pos: egui::Pos2) -> Option<Pos2> {
+        let p = self.screen_transform().inverse().transform_pos(pos);
+        let p = pos2(p.x, -p.y);
+        on_canvas(p).then_some(p)
+    }
+
+    fn screen_transform(&self) -> egui::emath::RectTransform {
         // !ASSUMPTION!
         // We asssume the graph_painter has an aspect ratio of 1:1. This is converted to
         // a (1,1) vector the center of which is (0.5, 0.5). We want the center to be at
@@ -389,7 +986,21 @@ impl App {
         let to_rect = self.graph_painter.as_ref().unwrap().clip_rect();
         let size = egui::Vec2 { x: 2.0, y: 2.0 };
         let from_rect = egui::Rect::from_center_size(egui::Pos2::ZERO, size);
-        let transform = egui::emath::RectTransform::from_to(from_rect, to_rect);
-        transform.transform_pos(egui::pos2(p.x, p.y * -1.0))
+        egui::emath::RectTransform::from_to(from_rect, to_rect)
     }
 }
+
+fn on_canvas(p: Pos2) -> bool {
+    p.x >= CANVAS_MIN.x && p.x <= CANVAS_MAX.x && p.y >= CANVAS_MIN.y && p.y <= CANVAS_MAX.y
+}
+
+/// The edges around each ring of the polygons.
+fn outlines(polygons: &[Polygon]) -> Vec<[Pos2; 2]> {
+    polygons
+        .iter()
+        .flat_map(|polygon| polygon.rings())
+        .flat_map(|ring| {
+            (0..ring.len()).map(|i| [ring[i], ring[(i + 1) % ring.len()]].map(|p| pos2(p.x, p.y)))
+        })
+        .collect()
+}
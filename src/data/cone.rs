@@ -1,4 +1,6 @@
-use crate::data::{PolarDirection, Vec2};
+use std::cmp::Ordering;
+
+use crate::data::{PolarDirection, Scalar, Vec2};
 
 // Cone example:
 // [ABC] ==
@@ -9,15 +11,15 @@ use crate::data::{PolarDirection, Vec2};
 //     /_____
 //    B      C
 #[derive(Debug, Clone, Copy, Default)]
-pub struct Cone {
-    pub a: Vec2,
-    pub b: Vec2,
-    pub origin: Vec2,
+pub struct Cone<T: Scalar = f32> {
+    pub a: Vec2<T>,
+    pub b: Vec2<T>,
+    pub origin: Vec2<T>,
 }
 
-impl Cone {
+impl<T: Scalar> Cone<T> {
     /// Returns the cosine angle of the cone.
-    pub fn angle(self) -> f32 {
+    pub fn angle(self) -> T {
         let Self { a, b, origin } = self;
         // Translate to the origin.
         let a = a.translate(origin);
@@ -31,22 +33,18 @@ impl Cone {
         // if it is positive, the three points constitute a "left turn" or
         // counter-clockwise orientation, otherwise a "right turn" or clockwise
         // orientation (for counter-clockwise numbered points).
+        // The sign of `b.cross(a)` (relative to the origin) is computed exactly, since
+        // rounding errors routinely flip it for nearly collinear points.
         let Self { a, b, origin } = self;
-        // Translate to the origin.
-        let a = a.translate(origin);
-        let b = b.translate(origin);
-        let angle = b.cross(a);
-        if angle == 0.0 {
-            PolarDirection::Collinear
-        } else if angle <= 0.0 {
-            PolarDirection::CW
-        } else {
-            PolarDirection::CCW
+        match T::orient2d(origin.into(), b.into(), a.into()) {
+            Ordering::Equal => PolarDirection::Collinear,
+            Ordering::Less => PolarDirection::CW,
+            Ordering::Greater => PolarDirection::CCW,
         }
     }
 }
 
-impl std::fmt::Display for Cone {
+impl<T: Scalar> std::fmt::Display for Cone<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} -> {} -> {}", self.a, self.origin, self.b)
     }
@@ -203,4 +201,24 @@ mod tests {
         .angle_direction();
         assert_eq!(ans, PolarDirection::Collinear);
     }
+
+    #[test]
+    fn test_angle_direction_nearly_collinear() {
+        // Kettner et al., "Classroom examples of robustness problems in geometric
+        // computations": points just next to the line y = x, where a plain f32 cross
+        // product gives wildly inconsistent answers.
+        let b = vec2(12.0, 12.0);
+        let origin = vec2(24.0, 24.0);
+        for i in 0..64 {
+            for j in 0..64 {
+                let a = vec2(0.5 + i as f32 * f32::EPSILON, 0.5 + j as f32 * f32::EPSILON);
+                let expected = match i.cmp(&j) {
+                    std::cmp::Ordering::Less => PolarDirection::CW,
+                    std::cmp::Ordering::Equal => PolarDirection::Collinear,
+                    std::cmp::Ordering::Greater => PolarDirection::CCW,
+                };
+                assert_eq!(Cone { a, b, origin }.angle_direction(), expected, "{a}");
+            }
+        }
+    }
 }
@@ -0,0 +1,382 @@
+use std::{cmp::Ordering, collections::HashMap, iter};
+
+use super::{
+    simplex::{LineSegment, Point, Triangle},
+    Scalar,
+};
+
+/// A doubly-connected edge list: a planar subdivision made up of vertices, faces, and
+/// the half-edges between them, which refer to each other by index.
+///
+/// Every edge is split into two half-edges running in opposite directions, each with
+/// the face to its left. Half-edges go counter-clockwise around the face they belong
+/// to, so around the boundary of the subdivision (which has no face, and is left as
+/// `None`) they go clockwise.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dcel<T: Scalar = f32> {
+    pub vertices: Vec<DcelVertex<T>>,
+    pub half_edges: Vec<HalfEdge>,
+    pub faces: Vec<DcelFace>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DcelVertex<T: Scalar = f32> {
+    pub point: Point<T>,
+    /// A half-edge leaving the vertex. For a vertex on the boundary it's the one just
+    /// counter-clockwise of the boundary, so that the half-edges around the vertex can
+    /// be gone through in order. A vertex that isn't part of any face has none.
+    pub half_edge: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HalfEdge {
+    /// The vertex the half-edge starts at.
+    pub origin: usize,
+    /// The half-edge running the other way along the same edge.
+    pub twin: usize,
+    /// The half-edges before and after this one around its face.
+    pub next: usize,
+    pub prev: usize,
+    /// The face to the left of the half-edge, if it isn't on the boundary.
+    pub face: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DcelFace {
+    /// One of the half-edges around the face.
+    pub half_edge: usize,
+}
+
+impl<T: Scalar> Dcel<T> {
+    /// Connects up a soup of triangles, merging the vertices they have in common. The
+    /// vertices end up sorted lexicographically, and degenerate triangles are dropped.
+    ///
+    /// # Panics
+    ///
+    /// If an edge is shared by more than two triangles, or the triangles sharing it
+    /// overlap.
+    pub fn from_triangles(triangles: &[Triangle<T>]) -> Self {
+        let mut points = triangles
+            .iter()
+            .flat_map(|t| t.vertices)
+            .collect::<Vec<_>>();
+        points.sort_by(Point::lexicographic);
+        points.dedup();
+        let index = |p: &Point<T>| points.binary_search_by(|q| q.lexicographic(p)).unwrap();
+
+        let faces = triangles
+            .iter()
+            .filter_map(|t| {
+                let [a, b, c] = t.vertices;
+                match T::orient2d(a.into(), b.into(), c.into()) {
+                    Ordering::Greater => Some([a, b, c]),
+                    Ordering::Less => Some([a, c, b]),
+                    Ordering::Equal => None,
+                }
+            })
+            .map(|t| t.map(|p| index(&p)))
+            .collect::<Vec<_>>();
+        Self::from_faces(points, &faces)
+    }
+
+    /// Builds the subdivision of `points` into `faces`, each of which lists the indices
+    /// of its vertices in counter-clockwise order.
+    ///
+    /// # Panics
+    ///
+    /// If an edge is shared by more than two faces, or by two faces going around it in
+    /// the same direction.
+    pub fn from_faces<F: AsRef<[usize]>>(points: Vec<Point<T>>, faces: &[F]) -> Self {
+        let mut dcel = Self {
+            vertices: points
+                .into_iter()
+                .map(|point| DcelVertex {
+                    point,
+                    half_edge: None,
+                })
+                .collect(),
+            half_edges: vec![],
+            faces: vec![],
+        };
+
+        // The half-edges around the faces, looked up by their endpoints.
+        let mut edges = HashMap::new();
+        for face in faces {
+            let face = face.as_ref();
+            let first = dcel.half_edges.len();
+            let n = face.len();
+            for (i, &origin) in face.iter().enumerate() {
+                let previous = edges.insert((origin, face[(i + 1) % n]), first + i);
+                assert!(previous.is_none(), "faces overlap along an edge");
+                dcel.half_edges.push(HalfEdge {
+                    origin,
+                    twin: usize::MAX,
+                    next: first + (i + 1) % n,
+                    prev: first + (i + n - 1) % n,
+                    face: Some(dcel.faces.len()),
+                });
+                dcel.vertices[origin].half_edge = Some(first + i);
+            }
+            dcel.faces.push(DcelFace { half_edge: first });
+        }
+
+        // Pair the half-edges up, adding boundary half-edges where there's nothing to
+        // pair with.
+        for e in 0..dcel.half_edges.len() {
+            if dcel.half_edges[e].twin != usize::MAX {
+                continue;
+            }
+            let HalfEdge { origin, next, .. } = dcel.half_edges[e];
+            let (a, b) = (origin, dcel.half_edges[next].origin);
+            let twin = match edges.get(&(b, a)) {
+                Some(&twin) => twin,
+                None => {
+                    dcel.half_edges.push(HalfEdge {
+                        origin: b,
+                        twin: e,
+                        next: usize::MAX,
+                        prev: usize::MAX,
+                        face: None,
+                    });
+                    dcel.half_edges.len() - 1
+                }
+            };
+            dcel.half_edges[e].twin = twin;
+            dcel.half_edges[twin].twin = e;
+        }
+
+        // Link up the boundary: the half-edge after one ending at a vertex is the next
+        // boundary half-edge counter-clockwise around that vertex.
+        for e in 0..dcel.half_edges.len() {
+            if dcel.half_edges[e].face.is_some() {
+                continue;
+            }
+            let mut next = dcel.half_edges[e].twin;
+            while dcel.half_edges[next].face.is_some() {
+                next = dcel.half_edges[dcel.half_edges[next].prev].twin;
+            }
+            dcel.half_edges[e].next = next;
+            dcel.half_edges[next].prev = e;
+            let origin = dcel.half_edges[next].origin;
+            dcel.vertices[origin].half_edge = Some(dcel.half_edges[e].twin);
+        }
+        dcel
+    }
+
+    /// The vertex the half-edge `e` ends at.
+    pub fn destination(&self, e: usize) -> usize {
+        self.half_edges[self.half_edges[e].twin].origin
+    }
+
+    /// The half-edge `e` as a segment from its origin to its destination.
+    pub fn segment(&self, e: usize) -> LineSegment<T> {
+        let vertices = [self.half_edges[e].origin, self.destination(e)];
+        LineSegment {
+            vertices: vertices.map(|v| self.vertices[v].point),
+        }
+    }
+
+    /// The half-edges around `face`, counter-clockwise.
+    pub fn face_half_edges(&self, face: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.faces[face].half_edge;
+        iter::successors(Some(start), move |&e| {
+            Some(self.half_edges[e].next).filter(|&e| e != start)
+        })
+    }
+
+    /// The vertices of `face`, counter-clockwise.
+    pub fn face_vertices(&self, face: usize) -> impl Iterator<Item = usize> + '_ {
+        self.face_half_edges(face)
+            .map(|e| self.half_edges[e].origin)
+    }
+
+    /// The faces across the edges of `face`, in counter-clockwise order. Faces sharing
+    /// several edges with it come up once for each of them.
+    pub fn face_neighbours(&self, face: usize) -> impl Iterator<Item = usize> + '_ {
+        self.face_half_edges(face)
+            .filter_map(|e| self.half_edges[self.half_edges[e].twin].face)
+    }
+
+    /// The half-edges leaving `vertex`, counter-clockwise around it, starting just after
+    /// the boundary if the vertex is on it (which makes the last one a boundary edge).
+    /// Around a vertex where the subdivision only touches itself, this only goes around
+    /// one of the fans of faces meeting there.
+    pub fn outgoing(&self, vertex: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.vertices[vertex].half_edge;
+        iter::successors(start, move |&e| {
+            let e = self.half_edges[self.half_edges[e].prev].twin;
+            Some(e).filter(|&e| Some(e) != start)
+        })
+    }
+
+    /// The vertices connected to `vertex` by an edge, in the order of [`Self::outgoing`].
+    pub fn vertex_ring(&self, vertex: usize) -> impl Iterator<Item = usize> + '_ {
+        self.outgoing(vertex).map(|e| self.destination(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    fn triangle(a: Point<f64>, b: Point<f64>, c: Point<f64>) -> Triangle<f64> {
+        Triangle {
+            vertices: [a, b, c],
+        }
+    }
+
+    /// Checks that the half-edges are linked up consistently.
+    fn check(dcel: &Dcel<f64>) {
+        for (e, h) in dcel.half_edges.iter().enumerate() {
+            assert_ne!(h.twin, e);
+            assert_eq!(dcel.half_edges[h.twin].twin, e);
+            assert_eq!(dcel.half_edges[h.next].prev, e);
+            assert_eq!(dcel.half_edges[h.prev].next, e);
+            assert_eq!(dcel.half_edges[h.next].origin, dcel.destination(e));
+            assert_eq!(dcel.half_edges[h.next].face, h.face);
+        }
+        for (f, face) in dcel.faces.iter().enumerate() {
+            assert_eq!(dcel.half_edges[face.half_edge].face, Some(f));
+        }
+        for (v, vertex) in dcel.vertices.iter().enumerate() {
+            if let Some(e) = vertex.half_edge {
+                assert_eq!(dcel.half_edges[e].origin, v);
+            }
+        }
+    }
+
+    #[test]
+    fn test_square() {
+        // Two triangles, the second one clockwise, sharing the diagonal of a square.
+        let [a, b, c, d] = [
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(1.0, 1.0),
+            point(0.0, 1.0),
+        ];
+        let dcel = Dcel::from_triangles(&[triangle(a, b, c), triangle(a, d, c)]);
+        check(&dcel);
+        assert_eq!(dcel.vertices.len(), 4);
+        assert_eq!(dcel.faces.len(), 2);
+        assert_eq!(dcel.half_edges.len(), 10);
+
+        assert_eq!(dcel.face_neighbours(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(dcel.face_neighbours(1).collect::<Vec<_>>(), vec![0]);
+        for f in 0..2 {
+            let vertices = dcel
+                .face_vertices(f)
+                .map(|v| dcel.vertices[v].point)
+                .collect::<Vec<_>>();
+            let [a, b, c] = [vertices[0], vertices[1], vertices[2]];
+            assert_eq!(
+                f64::orient2d(a.into(), b.into(), c.into()),
+                Ordering::Greater
+            );
+        }
+
+        // The boundary goes clockwise around the square.
+        let boundary = dcel
+            .half_edges
+            .iter()
+            .position(|h| h.face.is_none())
+            .unwrap();
+        let mut e = boundary;
+        let mut around = vec![];
+        loop {
+            around.push(dcel.vertices[dcel.half_edges[e].origin].point);
+            e = dcel.half_edges[e].next;
+            if e == boundary {
+                break;
+            }
+        }
+        assert_eq!(around.len(), 4);
+        let [p, q, r] = [around[0], around[1], around[2]];
+        assert_eq!(f64::orient2d(p.into(), q.into(), r.into()), Ordering::Less);
+
+        // Vertex a is on the boundary, so its ring starts there.
+        let ring = dcel
+            .vertex_ring(0)
+            .map(|v| dcel.vertices[v].point)
+            .collect::<Vec<_>>();
+        assert_eq!(ring, vec![b, c, d]);
+        assert_eq!(
+            dcel.segment(dcel.vertices[0].half_edge.unwrap()).vertices,
+            [a, b]
+        );
+    }
+
+    #[test]
+    fn test_signed_zero() {
+        // The corner at the origin comes as -0.0 in one triangle and 0.0 in the other.
+        let triangles = [
+            triangle(point(-0.0, 0.0), point(1.0, 0.0), point(1.0, 1.0)),
+            triangle(point(0.0, 0.0), point(1.0, 1.0), point(0.0, 1.0)),
+        ];
+        let dcel = Dcel::from_triangles(&triangles);
+        check(&dcel);
+        assert_eq!(dcel.vertices.len(), 4);
+        assert_eq!(dcel.face_neighbours(0).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_fan() {
+        // Six triangles around the origin.
+        let center = point(0.0, 0.0);
+        let ring = (0..6)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::TAU / 6.0;
+                point(angle.cos(), angle.sin())
+            })
+            .collect::<Vec<_>>();
+        let triangles = (0..6)
+            .map(|i| triangle(center, ring[i], ring[(i + 1) % 6]))
+            .collect::<Vec<_>>();
+        let dcel = Dcel::from_triangles(&triangles);
+        check(&dcel);
+        assert_eq!(dcel.half_edges.len(), 24);
+
+        let v = dcel
+            .vertices
+            .iter()
+            .position(|v| v.point == center)
+            .unwrap();
+        let around = dcel
+            .vertex_ring(v)
+            .map(|v| dcel.vertices[v].point)
+            .collect::<Vec<_>>();
+        // Counter-clockwise, from wherever it starts.
+        assert_eq!(around.len(), 6);
+        let start = ring.iter().position(|p| *p == around[0]).unwrap();
+        for (i, p) in around.iter().enumerate() {
+            assert_eq!(*p, ring[(start + i) % 6]);
+        }
+        for f in 0..6 {
+            assert_eq!(dcel.face_neighbours(f).count(), 2);
+        }
+    }
+
+    #[test]
+    fn test_polygons() {
+        // A square with a triangle on top, sharing an edge, and an unused vertex.
+        let points = vec![
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(1.0, 1.0),
+            point(0.0, 1.0),
+            point(0.5, 2.0),
+            point(5.0, 5.0),
+        ];
+        let faces = [vec![0, 1, 2, 3], vec![3, 2, 4]];
+        let dcel = Dcel::from_faces(points, &faces);
+        check(&dcel);
+        assert_eq!(dcel.half_edges.len(), 12);
+        assert_eq!(dcel.face_vertices(0).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(dcel.face_neighbours(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(dcel.vertices[5].half_edge, None);
+        assert_eq!(dcel.outgoing(5).count(), 0);
+    }
+}
@@ -0,0 +1,19 @@
+use super::point::Point3;
+use super::Scalar;
+
+/// A triangle mesh, with each face given by the indices of its three vertices.
+///
+/// Meshes that bound a volume list the vertices of each face counter-clockwise as seen
+/// from outside, so the normal `(b - a) x (c - a)` of a face `[a, b, c]` points outwards.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh<T: Scalar = f32> {
+    pub vertices: Vec<Point3<T>>,
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl<T: Scalar> Mesh<T> {
+    /// The vertices of `face`.
+    pub fn face(&self, face: usize) -> [Point3<T>; 3] {
+        self.faces[face].map(|v| self.vertices[v])
+    }
+}
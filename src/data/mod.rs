@@ -1,14 +1,24 @@
 pub mod cone;
+pub mod dcel;
 pub mod dot;
+pub mod mesh;
 pub mod point;
 pub mod polar_direction;
+pub mod polygon;
 pub mod pos2;
+pub mod scalar;
 pub mod simplex;
 pub mod sphere;
 pub mod vec2;
+pub mod voronoi;
 
 pub use cone::Cone;
+pub use dcel::{Dcel, DcelFace, DcelVertex, HalfEdge};
 pub use dot::Dot;
+pub use mesh::Mesh;
 pub use polar_direction::PolarDirection;
+pub use polygon::{PointLocation, Polygon};
 pub use pos2::{pos2, Pos2};
+pub use scalar::Scalar;
 pub use vec2::{vec2, Vec2};
+pub use voronoi::{Voronoi, VoronoiEdge};
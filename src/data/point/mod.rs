@@ -1,5 +1,7 @@
+#[allow(clippy::module_inception)]
 pub mod point;
 pub mod point2;
 pub mod point3;
 
 pub use point2::{point2, Point2};
+pub use point3::{point3, Point3};
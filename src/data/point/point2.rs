@@ -1,19 +1,21 @@
+use crate::data::Scalar;
+
 use super::point::Point;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
-pub struct Point2 {
-    pub x: f32,
-    pub y: f32,
+pub struct Point2<T: Scalar = f32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl std::fmt::Display for Point2 {
+impl<T: Scalar> std::fmt::Display for Point2<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
 
-pub fn point2(x: f32, y: f32) -> Point2 {
+pub fn point2<T: Scalar>(x: T, y: T) -> Point2<T> {
     Point2 { x, y }
 }
 
-impl Point for Point2 {}
+impl<T: Scalar> Point for Point2<T> {}
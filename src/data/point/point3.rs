@@ -1,20 +1,65 @@
+use std::cmp::Ordering;
+
+use crate::data::{simplex::Simplex, sphere::Sphere2, Scalar};
+
 use super::point::Point;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
-pub struct Point3 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+pub struct Point3<T: Scalar = f32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-impl std::fmt::Display for Point3 {
+impl<T: Scalar> std::fmt::Display for Point3<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {}, {})", self.x, self.y, self.z)
     }
 }
 
-pub fn point3(x: f32, y: f32, z: f32) -> Point3 {
+pub fn point3<T: Scalar>(x: T, y: T, z: T) -> Point3<T> {
     Point3 { x, y, z }
 }
 
-impl Point for Point3 {}
+impl<T: Scalar> Point3<T> {
+    /// Orders points by x, then by y and then by z, with -0.0 the same as 0.0.
+    pub fn lexicographic(&self, other: &Self) -> Ordering {
+        let x = self.x.numeric_cmp(&other.x);
+        x.then_with(|| self.y.numeric_cmp(&other.y))
+            .then_with(|| self.z.numeric_cmp(&other.z))
+    }
+}
+
+impl<T: Scalar> Point for Point3<T> {}
+
+impl<T: Scalar> Simplex<T> for Point3<T> {
+    type Vertex = Point3<T>;
+    type Face = Point3<T>;
+    type S = Sphere2<T>;
+
+    fn dimension() -> u32 {
+        0
+    }
+
+    fn faces(&self) -> Vec<Self::Face> {
+        vec![]
+    }
+
+    fn vertices(&self) -> &[Point3<T>] {
+        std::slice::from_ref(self)
+    }
+
+    fn volume(&self) -> T {
+        T::zero()
+    }
+
+    fn circumscribe(&self) -> Option<Self::S> {
+        None
+    }
+}
+
+impl<T: Scalar> From<Point3<T>> for [T; 3] {
+    fn from(point: Point3<T>) -> Self {
+        [point.x, point.y, point.z]
+    }
+}
@@ -0,0 +1,425 @@
+use std::cmp::Ordering;
+
+use super::{
+    simplex::{LineSegment, Point, SegmentIntersection},
+    PolarDirection, Scalar,
+};
+
+/// A polygon, given by the vertices around its boundary and around each of its holes.
+///
+/// Each ring of vertices is closed: its last vertex connects back to its first one.
+/// The usual convention, which `graham_scan_polygon` follows, is for the boundary to
+/// go counter-clockwise and the holes clockwise, but the methods here don't rely on it
+/// unless they say so.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Polygon<T: Scalar = f32> {
+    pub exterior: Vec<Point<T>>,
+    pub holes: Vec<Vec<Point<T>>>,
+}
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointLocation {
+    Inside,
+    OnBoundary,
+    Outside,
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// A polygon without holes.
+    pub fn new(exterior: Vec<Point<T>>) -> Self {
+        Self {
+            exterior,
+            holes: vec![],
+        }
+    }
+
+    /// The exterior followed by the holes.
+    pub fn rings(&self) -> impl Iterator<Item = &[Point<T>]> {
+        std::iter::once(&self.exterior[..]).chain(self.holes.iter().map(|hole| &hole[..]))
+    }
+
+    /// The area inside the exterior but outside the holes, which is positive when the
+    /// exterior goes counter-clockwise, and negative when it goes clockwise.
+    pub fn signed_area(&self) -> T {
+        let exterior = ring_area(&self.exterior);
+        let holes = self
+            .holes
+            .iter()
+            .fold(T::zero(), |sum, hole| sum + ring_area(hole).abs());
+        if exterior < T::zero() {
+            exterior + holes
+        } else {
+            exterior - holes
+        }
+    }
+
+    /// Which way the exterior goes around, decided exactly at its lowest vertex (which
+    /// is convex). This can only be `Collinear` if the polygon has no area.
+    pub fn orientation(&self) -> PolarDirection {
+        orientation(&self.exterior)
+    }
+
+    /// Whether every ring is a simple closed curve, with at least three vertices, and
+    /// the rings don't touch each other. Whether the holes are inside the exterior is
+    /// not checked. The check is exact, and compares every pair of edges.
+    pub fn is_simple(&self) -> bool {
+        let rings = self.rings().collect::<Vec<_>>();
+        if rings
+            .iter()
+            .any(|ring| ring.len() < 3 || (0..ring.len()).any(|i| ring[i] == next(ring, i)))
+        {
+            return false;
+        }
+        // The edges as (ring, index) pairs, each running from the vertex at that index.
+        let edges = rings
+            .iter()
+            .enumerate()
+            .flat_map(|(r, ring)| (0..ring.len()).map(move |i| (r, i)))
+            .collect::<Vec<_>>();
+        let segment = |(r, i): (usize, usize)| LineSegment {
+            vertices: [rings[r][i], next(rings[r], i)],
+        };
+        for (j, &(r, i)) in edges.iter().enumerate() {
+            for &(s, k) in &edges[j + 1..] {
+                let meet = segment((r, i)).intersection_point(&segment((s, k)));
+                // Consecutive edges only share the vertex between them.
+                let n = rings[r].len();
+                let shared = if r != s {
+                    None
+                } else if k == (i + 1) % n {
+                    Some(rings[r][k])
+                } else if i == (k + 1) % n {
+                    Some(rings[r][i])
+                } else {
+                    None
+                };
+                let allowed = match shared {
+                    Some(vertex) => SegmentIntersection::Point(vertex),
+                    None => SegmentIntersection::None,
+                };
+                if meet != allowed {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// The center of mass of the polygon, or `None` if it has no area. Holes are taken
+    /// out whichever way they go around.
+    pub fn centroid(&self) -> Option<Point<T>> {
+        let (mut area, mut x, mut y) = (T::zero(), T::zero(), T::zero());
+        for (i, ring) in self.rings().enumerate() {
+            // Make the exterior count positively, and the holes negatively.
+            let ring_area = ring_area(ring);
+            let flip = if i == 0 {
+                ring_area < T::zero()
+            } else {
+                ring_area > T::zero()
+            };
+            let (mut ring_x, mut ring_y) = (T::zero(), T::zero());
+            for (j, a) in ring.iter().enumerate() {
+                let b = next(ring, j);
+                let cross = a.x * b.y - b.x * a.y;
+                ring_x = ring_x + (a.x + b.x) * cross;
+                ring_y = ring_y + (a.y + b.y) * cross;
+            }
+            let sign = if flip { -T::one() } else { T::one() };
+            area = area + ring_area * sign;
+            x = x + ring_x * sign;
+            y = y + ring_y * sign;
+        }
+        if area == T::zero() {
+            return None;
+        }
+        let six = T::from_f64(6.0);
+        Some(Point {
+            x: x / (six * area),
+            y: y / (six * area),
+        })
+    }
+
+    /// How many times the rings wind around p, counting counter-clockwise turns as
+    /// positive. With holes going around the other way to the exterior this is zero
+    /// inside them. Points on the boundary count however the edges through them fall.
+    pub fn winding_number(&self, p: Point<T>) -> i32 {
+        self.rings().map(|ring| winding_number(ring, p)).sum()
+    }
+
+    /// Whether p is inside the polygon, outside it, or on its boundary, decided exactly.
+    /// Being inside means being inside the exterior, and outside each of the holes,
+    /// whichever way they go around.
+    pub fn locate(&self, p: Point<T>) -> PointLocation {
+        let on_boundary = self.rings().any(|ring| {
+            (0..ring.len()).any(|i| {
+                let segment = LineSegment {
+                    vertices: [ring[i], next(ring, i)],
+                };
+                let point = LineSegment { vertices: [p, p] };
+                segment.intersection_point(&point) != SegmentIntersection::None
+            })
+        });
+        if on_boundary {
+            PointLocation::OnBoundary
+        } else if winding_number(&self.exterior, p) != 0
+            && self.holes.iter().all(|hole| winding_number(hole, p) == 0)
+        {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+}
+
+/// The vertex after the one at index `i`.
+fn next<T: Scalar>(ring: &[Point<T>], i: usize) -> Point<T> {
+    ring[(i + 1) % ring.len()]
+}
+
+/// The signed area of a ring, by the shoelace formula.
+fn ring_area<T: Scalar>(ring: &[Point<T>]) -> T {
+    let twice = (0..ring.len()).fold(T::zero(), |sum, i| {
+        let (a, b) = (ring[i], next(ring, i));
+        sum + (a.x * b.y - b.x * a.y)
+    });
+    twice / T::from_f64(2.0)
+}
+
+fn orientation<T: Scalar>(ring: &[Point<T>]) -> PolarDirection {
+    let lowest = (0..ring.len()).min_by(|&i, &j| {
+        let (p, q) = (ring[i], ring[j]);
+        p.y.numeric_cmp(&q.y).then_with(|| p.x.numeric_cmp(&q.x))
+    });
+    let Some(i) = lowest else {
+        return PolarDirection::Collinear;
+    };
+    let prev = ring[(i + ring.len() - 1) % ring.len()];
+    match T::orient2d(prev.into(), ring[i].into(), next(ring, i).into()) {
+        Ordering::Greater => PolarDirection::CCW,
+        Ordering::Less => PolarDirection::CW,
+        Ordering::Equal => PolarDirection::Collinear,
+    }
+}
+
+// https://en.wikipedia.org/wiki/Point_in_polygon#Winding_number_algorithm
+//
+// Counts the edges crossing the horizontal ray to the right of p, upwards ones
+// positively and downwards ones negatively. Edges are half-open, including their lower
+// endpoint but not their upper one, so a vertex on the ray is only counted once.
+fn winding_number<T: Scalar>(ring: &[Point<T>], p: Point<T>) -> i32 {
+    let mut winding = 0;
+    for (i, a) in ring.iter().enumerate() {
+        let b = next(ring, i);
+        let side = T::orient2d((*a).into(), b.into(), p.into());
+        if a.y <= p.y {
+            if b.y > p.y && side == Ordering::Greater {
+                winding += 1;
+            }
+        } else if b.y <= p.y && side == Ordering::Less {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use rand::{rngs::StdRng, Rng};
+
+    use super::*;
+    use crate::data::simplex::{Simplex, Triangle};
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    /// Checks that the triangles of a triangulation of the polygon are
+    /// counter-clockwise, that there are as many as there should be, and that they make
+    /// up the area of the polygon.
+    pub(crate) fn check_triangulation(polygon: &Polygon<f64>, triangles: &[Triangle<f64>]) {
+        for t in triangles {
+            let [a, b, c] = t.vertices;
+            assert_eq!(
+                f64::orient2d(a.into(), b.into(), c.into()),
+                Ordering::Greater
+            );
+        }
+        let n = polygon.rings().map(|ring| ring.len()).sum::<usize>();
+        assert_eq!(triangles.len(), n + 2 * polygon.holes.len() - 2);
+        let area = triangles.iter().map(|t| t.volume()).sum::<f64>();
+        let expected = polygon.signed_area().abs();
+        assert!(
+            (area - expected).abs() < 1e-9 * expected,
+            "{area} != {expected}"
+        );
+    }
+
+    /// A comb with five teeth pointing up, which only has ears at its teeth.
+    pub(crate) fn comb() -> Polygon<f64> {
+        let mut exterior = vec![point(10.0, 0.0), point(10.0, 5.0)];
+        for i in (0..5).rev() {
+            let x = i as f64 * 2.0;
+            exterior.extend([point(x + 1.0, 5.0), point(x + 0.5, 1.0), point(x, 5.0)]);
+        }
+        exterior.push(point(0.0, 0.0));
+        Polygon::new(exterior)
+    }
+
+    /// A random star-shaped ring of `n` vertices going counter-clockwise around
+    /// `center`, at between half and all of `scale` from it.
+    pub(crate) fn star(
+        rng: &mut StdRng,
+        n: usize,
+        center: Point<f64>,
+        scale: f64,
+    ) -> Vec<Point<f64>> {
+        (0..n)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::TAU / n as f64;
+                let radius = scale * rng.gen_range(0.5..1.0);
+                point(
+                    center.x + radius * angle.cos(),
+                    center.y + radius * angle.sin(),
+                )
+            })
+            .collect()
+    }
+
+    /// A 4 by 4 square with a 2 by 2 hole in the middle, going around the usual ways.
+    fn frame() -> Polygon<f64> {
+        Polygon {
+            exterior: vec![
+                point(0.0, 0.0),
+                point(4.0, 0.0),
+                point(4.0, 4.0),
+                point(0.0, 4.0),
+            ],
+            holes: vec![vec![
+                point(1.0, 1.0),
+                point(1.0, 3.0),
+                point(3.0, 3.0),
+                point(3.0, 1.0),
+            ]],
+        }
+    }
+
+    #[test]
+    fn test_area_and_orientation() {
+        let mut polygon = frame();
+        assert_eq!(polygon.signed_area(), 12.0);
+        assert_eq!(polygon.orientation(), PolarDirection::CCW);
+
+        // The hole is taken out whichever way it goes.
+        polygon.holes[0].reverse();
+        assert_eq!(polygon.signed_area(), 12.0);
+        polygon.exterior.reverse();
+        assert_eq!(polygon.signed_area(), -12.0);
+        assert_eq!(polygon.orientation(), PolarDirection::CW);
+
+        let flat = Polygon::new(vec![point(0.0, 0.0), point(1.0, 0.0), point(2.0, 0.0)]);
+        assert_eq!(flat.orientation(), PolarDirection::Collinear);
+        assert_eq!(flat.signed_area(), 0.0);
+    }
+
+    #[test]
+    fn test_centroid() {
+        assert_eq!(frame().centroid(), Some(point(2.0, 2.0)));
+
+        // An L made up of a 2 by 1 and a 1 by 1 rectangle.
+        let l = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 1.0),
+            point(1.0, 1.0),
+            point(1.0, 2.0),
+            point(0.0, 2.0),
+        ]);
+        let expected = point((2.0 * 1.0 + 0.5) / 3.0, (2.0 * 0.5 + 1.5) / 3.0);
+        let centroid = l.centroid().unwrap();
+        assert!((centroid.x - expected.x).abs() < 1e-12);
+        assert!((centroid.y - expected.y).abs() < 1e-12);
+
+        let flat = Polygon::new(vec![point(0.0, 0.0), point(1.0, 0.0), point(2.0, 0.0)]);
+        assert_eq!(flat.centroid(), None);
+    }
+
+    #[test]
+    fn test_is_simple() {
+        assert!(frame().is_simple());
+
+        // A bowtie.
+        let bowtie = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(1.0, 1.0),
+            point(1.0, 0.0),
+            point(0.0, 1.0),
+        ]);
+        assert!(!bowtie.is_simple());
+
+        // A ring that doubles back on itself, and one touching itself at a vertex.
+        let spike = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(1.0, 0.0),
+            point(1.0, 1.0),
+        ]);
+        assert!(!spike.is_simple());
+        let pinched = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(1.0, 1.0),
+            point(2.0, 2.0),
+            point(0.0, 2.0),
+            point(1.0, 1.0),
+        ]);
+        assert!(!pinched.is_simple());
+
+        // A hole touching the exterior.
+        let mut polygon = frame();
+        polygon.holes[0][0] = point(0.0, 1.0);
+        assert!(!polygon.is_simple());
+
+        let repeated = Polygon::new(vec![
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(1.0, 0.0),
+            point(0.0, 1.0),
+        ]);
+        assert!(!repeated.is_simple());
+    }
+
+    #[test]
+    fn test_locate() {
+        let polygon = frame();
+        assert_eq!(polygon.locate(point(0.5, 0.5)), PointLocation::Inside);
+        assert_eq!(polygon.locate(point(2.0, 2.0)), PointLocation::Outside);
+        assert_eq!(polygon.locate(point(5.0, 2.0)), PointLocation::Outside);
+        assert_eq!(polygon.locate(point(4.0, 2.0)), PointLocation::OnBoundary);
+        assert_eq!(polygon.locate(point(1.0, 1.0)), PointLocation::OnBoundary);
+        // On the same line as the horizontal edges, and through vertices.
+        assert_eq!(polygon.locate(point(-1.0, 0.0)), PointLocation::Outside);
+        assert_eq!(polygon.locate(point(0.5, 1.0)), PointLocation::Inside);
+        assert_eq!(polygon.locate(point(0.5, 3.0)), PointLocation::Inside);
+
+        assert_eq!(polygon.winding_number(point(0.5, 0.5)), 1);
+        assert_eq!(polygon.winding_number(point(2.0, 2.0)), 0);
+        assert_eq!(polygon.winding_number(point(5.0, 2.0)), 0);
+    }
+
+    #[test]
+    fn test_winding_twice() {
+        // A pentagram, with its center wound around twice.
+        let star = (0..5)
+            .map(|i| {
+                let angle =
+                    std::f64::consts::FRAC_PI_2 + i as f64 * 2.0 * std::f64::consts::TAU / 5.0;
+                point(angle.cos(), angle.sin())
+            })
+            .collect();
+        let star = Polygon::new(star);
+        assert_eq!(star.winding_number(point(0.0, 0.0)), 2);
+        assert_eq!(star.winding_number(point(0.0, 0.9)), 1);
+        assert!(!star.is_simple());
+    }
+}
@@ -1,25 +1,33 @@
-use std::ops::Sub;
+use std::{cmp::Ordering, ops::Sub};
 
-use super::Vec2;
+use super::{Scalar, Vec2};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
-pub struct Pos2 {
-    pub x: f32,
-    pub y: f32,
+pub struct Pos2<T: Scalar = f32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Into<Vec2> for Pos2 {
-    fn into(self) -> Vec2 {
-        let Pos2 { x, y } = self;
+impl<T: Scalar> From<Pos2<T>> for Vec2<T> {
+    fn from(pos: Pos2<T>) -> Self {
+        let Pos2 { x, y } = pos;
         Vec2 { x, y }
     }
 }
 
-pub fn pos2(x: f32, y: f32) -> Pos2 {
+impl<T: Scalar> Pos2<T> {
+    /// Orders points by x and then by y, with -0.0 the same as 0.0.
+    pub fn lexicographic(&self, other: &Self) -> Ordering {
+        let x = self.x.numeric_cmp(&other.x);
+        x.then_with(|| self.y.numeric_cmp(&other.y))
+    }
+}
+
+pub fn pos2<T: Scalar>(x: T, y: T) -> Pos2<T> {
     Pos2 { x, y }
 }
 
-impl Sub for Pos2 {
+impl<T: Scalar> Sub for Pos2<T> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -0,0 +1,303 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use num_rational::{BigRational, Rational64};
+
+use crate::predicates;
+
+/// The number type the geometry primitives and algorithms are built on.
+///
+/// Besides the field operations, a scalar has to answer the orientation and in-circle
+/// predicates exactly, which is what keeps the algorithms combinatorially correct.
+/// Floats do so adaptively (see [`predicates`]), exact types by plain arithmetic.
+pub trait Scalar:
+    Copy
+    + Debug
+    + Display
+    + Default
+    + PartialEq
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Converts an f64, rounding it if it can't be represented.
+    fn from_f64(value: f64) -> Self;
+    /// Converts to an f64, rounding if needed.
+    fn to_f64(self) -> f64;
+    /// Converts to an f64, unless that would need rounding.
+    fn to_exact_f64(self) -> Option<f64>;
+
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    fn one() -> Self {
+        Self::from_f64(1.0)
+    }
+
+    /// A total order, which agrees with `PartialOrd` where that is defined.
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    /// `total_cmp`, except that -0.0 and 0.0 are equal, as they are for `==`. Computed
+    /// coordinates can come out as either, so points are compared with this.
+    fn numeric_cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else {
+            self.total_cmp(other)
+        }
+    }
+    fn is_finite(self) -> bool;
+    fn abs(self) -> Self;
+    /// Square roots of exact types are rounded.
+    fn sqrt(self) -> Self;
+
+    fn hypot(self, other: Self) -> Self {
+        (self * self + other * other).sqrt()
+    }
+
+    /// `Greater` if `a`, `b` and `c` occur in counter-clockwise order, `Less` if they
+    /// occur in clockwise order and `Equal` if they are collinear.
+    fn orient2d(a: [Self; 2], b: [Self; 2], c: [Self; 2]) -> Ordering;
+
+    /// `Greater` if `d` lies below the plane through `a`, `b` and `c`, `Less` if it lies
+    /// above it and `Equal` if the four points are coplanar. Above is the side from
+    /// which `a`, `b` and `c` appear in counter-clockwise order.
+    fn orient3d(a: [Self; 3], b: [Self; 3], c: [Self; 3], d: [Self; 3]) -> Ordering;
+
+    /// `Greater` if `d` lies inside the circle passing through the counter-clockwise
+    /// `a`, `b` and `c`, `Less` if it lies outside and `Equal` if it lies on it.
+    fn incircle(a: [Self; 2], b: [Self; 2], c: [Self; 2], d: [Self; 2]) -> Ordering;
+
+    /// `Greater` if `e` lies inside the sphere passing through `a`, `b`, `c` and `d`,
+    /// `Less` if it lies outside and `Equal` if it lies on it. `d` has to lie below the
+    /// plane through `a`, `b` and `c`, as in `orient3d`.
+    fn insphere(a: [Self; 3], b: [Self; 3], c: [Self; 3], d: [Self; 3], e: [Self; 3]) -> Ordering;
+}
+
+macro_rules! impl_float_scalar {
+    ($t:ty) => {
+        impl Scalar for $t {
+            fn from_f64(value: f64) -> Self {
+                value as $t
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn to_exact_f64(self) -> Option<f64> {
+                Some(self as f64)
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                <$t>::total_cmp(self, other)
+            }
+
+            fn is_finite(self) -> bool {
+                <$t>::is_finite(self)
+            }
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn sqrt(self) -> Self {
+                <$t>::sqrt(self)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                <$t>::hypot(self, other)
+            }
+
+            fn orient2d(a: [Self; 2], b: [Self; 2], c: [Self; 2]) -> Ordering {
+                let det =
+                    predicates::orient2d(a.map(f64::from), b.map(f64::from), c.map(f64::from));
+                det.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+            }
+
+            fn orient3d(a: [Self; 3], b: [Self; 3], c: [Self; 3], d: [Self; 3]) -> Ordering {
+                let det = predicates::orient3d(
+                    a.map(f64::from),
+                    b.map(f64::from),
+                    c.map(f64::from),
+                    d.map(f64::from),
+                );
+                det.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+            }
+
+            fn incircle(a: [Self; 2], b: [Self; 2], c: [Self; 2], d: [Self; 2]) -> Ordering {
+                let det = predicates::incircle(
+                    a.map(f64::from),
+                    b.map(f64::from),
+                    c.map(f64::from),
+                    d.map(f64::from),
+                );
+                det.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+            }
+
+            fn insphere(
+                a: [Self; 3],
+                b: [Self; 3],
+                c: [Self; 3],
+                d: [Self; 3],
+                e: [Self; 3],
+            ) -> Ordering {
+                let det = predicates::insphere(
+                    a.map(f64::from),
+                    b.map(f64::from),
+                    c.map(f64::from),
+                    d.map(f64::from),
+                    e.map(f64::from),
+                );
+                det.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+            }
+        }
+    };
+}
+
+impl_float_scalar!(f32);
+impl_float_scalar!(f64);
+
+/// Rationals are exact, but the determinants of the predicates easily overflow an i64,
+/// so those are evaluated with big integers.
+impl Scalar for Rational64 {
+    fn from_f64(value: f64) -> Self {
+        Rational64::approximate_float(value).expect("the value is out of range")
+    }
+
+    fn to_f64(self) -> f64 {
+        *self.numer() as f64 / *self.denom() as f64
+    }
+
+    fn to_exact_f64(self) -> Option<f64> {
+        let exact = self.numer().unsigned_abs() < 1 << f64::MANTISSA_DIGITS
+            && self.denom().unsigned_abs().is_power_of_two();
+        exact.then(|| self.to_f64())
+    }
+
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    fn is_finite(self) -> bool {
+        true
+    }
+
+    fn abs(self) -> Self {
+        if self < Self::zero() {
+            -self
+        } else {
+            self
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        Self::from_f64(self.to_f64().sqrt())
+    }
+
+    fn orient2d(a: [Self; 2], b: [Self; 2], c: [Self; 2]) -> Ordering {
+        let [a, b, c] = [a, b, c].map(|p| p.map(big));
+        let det = (&a[0] - &c[0]) * (&b[1] - &c[1]) - (&a[1] - &c[1]) * (&b[0] - &c[0]);
+        det.cmp(&BigRational::default())
+    }
+
+    fn orient3d(a: [Self; 3], b: [Self; 3], c: [Self; 3], d: [Self; 3]) -> Ordering {
+        let [a, b, c, d] = [a, b, c, d].map(|p| p.map(big));
+        let [a, b, c] = [a, b, c].map(|p| [&p[0] - &d[0], &p[1] - &d[1], &p[2] - &d[2]]);
+        let cross = |p: &[BigRational; 3], q: &[BigRational; 3]| &p[0] * &q[1] - &p[1] * &q[0];
+        let det = &a[2] * cross(&b, &c) + &b[2] * cross(&c, &a) + &c[2] * cross(&a, &b);
+        det.cmp(&BigRational::default())
+    }
+
+    fn incircle(a: [Self; 2], b: [Self; 2], c: [Self; 2], d: [Self; 2]) -> Ordering {
+        let [a, b, c, d] = [a, b, c, d].map(|p| p.map(big));
+        let [a, b, c] = [a, b, c].map(|p| [&p[0] - &d[0], &p[1] - &d[1]]);
+        let lift = |p: &[BigRational; 2]| &p[0] * &p[0] + &p[1] * &p[1];
+        let cross = |p: &[BigRational; 2], q: &[BigRational; 2]| &p[0] * &q[1] - &p[1] * &q[0];
+        let det = lift(&a) * cross(&b, &c) + lift(&b) * cross(&c, &a) + lift(&c) * cross(&a, &b);
+        det.cmp(&BigRational::default())
+    }
+
+    fn insphere(a: [Self; 3], b: [Self; 3], c: [Self; 3], d: [Self; 3], e: [Self; 3]) -> Ordering {
+        let [a, b, c, d, e] = [a, b, c, d, e].map(|p| p.map(big));
+        let [a, b, c, d] = [a, b, c, d].map(|p| [&p[0] - &e[0], &p[1] - &e[1], &p[2] - &e[2]]);
+        let lift = |p: &[BigRational; 3]| &p[0] * &p[0] + &p[1] * &p[1] + &p[2] * &p[2];
+        let cross = |p: &[BigRational; 3], q: &[BigRational; 3]| &p[0] * &q[1] - &p[1] * &q[0];
+        // The orientation of `p`, `q` and `r` as seen from `e`.
+        let orient = |p: &[BigRational; 3], q: &[BigRational; 3], r: &[BigRational; 3]| {
+            &p[2] * cross(q, r) + &q[2] * cross(r, p) + &r[2] * cross(p, q)
+        };
+        let det = lift(&d) * orient(&a, &b, &c) - lift(&c) * orient(&d, &a, &b)
+            + lift(&b) * orient(&c, &d, &a)
+            - lift(&a) * orient(&b, &c, &d);
+        det.cmp(&BigRational::default())
+    }
+}
+
+fn big(r: Rational64) -> BigRational {
+    BigRational::new((*r.numer()).into(), (*r.denom()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rational_predicates() {
+        // The determinants of these would overflow an i64 many times over.
+        let big = |n: i64, d: i64| Rational64::new(n, d);
+        let (u, v) = (big(i64::MAX / 3, 7), big(1, i64::MAX / 5));
+        let origin = [big(0, 1), big(0, 1)];
+        assert_eq!(
+            Rational64::orient2d(origin, [u, v], [-u, -v]),
+            Ordering::Equal
+        );
+        let c = [-u, -v / big(2, 1)];
+        assert_eq!(Rational64::orient2d(origin, [u, v], c), Ordering::Greater);
+
+        let on = [big(3, 1), big(4, 1)];
+        let circle = [
+            [big(5, 1), big(0, 1)],
+            [big(0, 1), big(5, 1)],
+            [big(-5, 1), big(0, 1)],
+        ];
+        let [p, q, r] = circle;
+        assert_eq!(Rational64::incircle(p, q, r, on), Ordering::Equal);
+        let inside = [big(3, 1), big(4, 1) - big(1, 1 << 40)];
+        assert_eq!(Rational64::incircle(p, q, r, inside), Ordering::Greater);
+        let outside = [big(3, 1), big(4, 1) + big(1, 1 << 40)];
+        assert_eq!(Rational64::incircle(p, q, r, outside), Ordering::Less);
+
+        let [o, x, y] = [[0, 0, 0], [1, 0, 0], [0, 1, 0]].map(|p| p.map(|c| big(c, 1)));
+        let up = [u, v, big(1, 1 << 40)];
+        assert_eq!(Rational64::orient3d(o, x, y, up), Ordering::Less);
+        assert_eq!(Rational64::orient3d(o, y, x, up), Ordering::Greater);
+        assert_eq!(
+            Rational64::orient3d(o, x, y, [u, v, big(0, 1)]),
+            Ordering::Equal
+        );
+
+        let sphere = [[5, 0, 0], [0, 5, 0], [0, 0, 5], [-5, 0, 0]].map(|p| p.map(|c| big(c, 1)));
+        let [p, q, r, s] = sphere;
+        let on = [big(0, 1), big(3, 1), big(-4, 1)];
+        assert_eq!(Rational64::insphere(p, q, r, s, on), Ordering::Equal);
+        let inside = [big(0, 1), big(3, 1), big(-4, 1) + big(1, 1 << 40)];
+        assert_eq!(Rational64::insphere(p, q, r, s, inside), Ordering::Greater);
+        let outside = [big(0, 1), big(3, 1), big(-4, 1) - big(1, 1 << 40)];
+        assert_eq!(Rational64::insphere(p, q, r, s, outside), Ordering::Less);
+    }
+
+    #[test]
+    fn test_to_exact_f64() {
+        assert_eq!(Rational64::new(3, 4).to_exact_f64(), Some(0.75));
+        assert_eq!(Rational64::new(1, 3).to_exact_f64(), None);
+        assert_eq!(0.1f32.to_exact_f64(), Some(0.1f32 as f64));
+    }
+}
@@ -1,41 +1,104 @@
-use crate::data::sphere::Sphere1;
+use std::cmp::Ordering;
+
+use crate::data::{sphere::Sphere1, Scalar};
 
 use super::{Point, Simplex};
 
-pub struct LineSegment {
-    pub vertices: [Point; 2],
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineSegment<T: Scalar = f32> {
+    pub vertices: [Point<T>; 2],
+}
+
+/// Where two line segments meet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegmentIntersection<T: Scalar = f32> {
+    /// The segments have no point in common.
+    None,
+    /// The segments meet at a single point.
+    Point(Point<T>),
+    /// The segments lie on the same line, and overlap along this segment.
+    Segment(LineSegment<T>),
 }
 
-impl LineSegment {
-    pub fn intersection_point(&self, other: &Self) -> Option<Point> {
+impl<T: Scalar> LineSegment<T> {
+    /// The points this segment and `other` have in common, endpoints included. An
+    /// overlap runs in the same direction as this segment.
+    ///
+    /// Whether the segments meet, and whether they do at an endpoint, is decided
+    /// exactly. Only the point where they cross (away from their endpoints) has to be
+    /// computed, and is rounded.
+    pub fn intersection_point(&self, other: &Self) -> SegmentIntersection<T> {
         let [a, b] = self.vertices;
         let [c, d] = other.vertices;
-        // Line AB represented as a1x + b1y = c1
-        let a1 = b.y - a.y;
-        let b1 = a.x - b.x;
-        let c1 = a1 * (a.x) + b1 * (a.y);
-
-        // Line CD represented as a2x + b2y = c2
-        let a2 = d.y - c.y;
-        let b2 = c.x - d.x;
-        let c2 = a2 * (c.x) + b2 * (c.y);
-
-        let determinant = a1 * b2 - a2 * b1;
-
-        if determinant == 0.0 {
-            // The lines are parallel.
-            None
-        } else {
-            let x = (b2 * c1 - b1 * c2) / determinant;
-            let y = (a1 * c2 - a2 * c1) / determinant;
-            Some(Point { x, y })
+        let orient =
+            |p: Point<T>, q: Point<T>, r: Point<T>| T::orient2d(p.into(), q.into(), r.into());
+        let [o1, o2, o3, o4] = [
+            orient(a, b, c),
+            orient(a, b, d),
+            orient(c, d, a),
+            orient(c, d, b),
+        ];
+
+        if [o1, o2, o3, o4].iter().all(|o| o.is_eq()) {
+            // All four points are on one line (or the segments are points), along which
+            // they are ordered lexicographically.
+            let sorted = |p: Point<T>, q: Point<T>| match p.lexicographic(&q) {
+                Ordering::Greater => [q, p],
+                _ => [p, q],
+            };
+            let ([s0, s1], [t0, t1]) = (sorted(a, b), sorted(c, d));
+            let start = if s0.lexicographic(&t0).is_lt() {
+                t0
+            } else {
+                s0
+            };
+            let end = if s1.lexicographic(&t1).is_lt() {
+                s1
+            } else {
+                t1
+            };
+            return match start.lexicographic(&end) {
+                Ordering::Greater => SegmentIntersection::None,
+                Ordering::Equal => SegmentIntersection::Point(start),
+                Ordering::Less if a.lexicographic(&b).is_gt() => {
+                    SegmentIntersection::Segment(LineSegment {
+                        vertices: [end, start],
+                    })
+                }
+                Ordering::Less => SegmentIntersection::Segment(LineSegment {
+                    vertices: [start, end],
+                }),
+            };
         }
+
+        // Otherwise each segment has to have its endpoints on either side of the other's
+        // line, or on it.
+        if (o1 == o2 && o1.is_ne()) || (o3 == o4 && o3.is_ne()) {
+            return SegmentIntersection::None;
+        }
+        let point = match [o1, o2, o3, o4] {
+            [Ordering::Equal, ..] => c,
+            [_, Ordering::Equal, ..] => d,
+            [_, _, Ordering::Equal, _] => a,
+            [.., Ordering::Equal] => b,
+            _ => {
+                // The segments cross, at the point where a + (b - a) * t is on cd.
+                let (u, v, w) = (b - a, d - c, c - a);
+                let t = (w.x * v.y - w.y * v.x) / (u.x * v.y - u.y * v.x);
+                Point {
+                    x: a.x + u.x * t,
+                    y: a.y + u.y * t,
+                }
+            }
+        };
+        SegmentIntersection::Point(point)
     }
 }
 
-impl Simplex for LineSegment {
-    type Face = Point;
-    type S = Sphere1;
+impl<T: Scalar> Simplex<T> for LineSegment<T> {
+    type Vertex = Point<T>;
+    type Face = Point<T>;
+    type S = Sphere1<T>;
 
     fn dimension() -> u32 {
         1
@@ -45,11 +108,11 @@ impl Simplex for LineSegment {
         vec![self.vertices[0], self.vertices[1]]
     }
 
-    fn vertices(&self) -> &[Point] {
+    fn vertices(&self) -> &[Point<T>] {
         &self.vertices
     }
 
-    fn volume(&self) -> f32 {
+    fn volume(&self) -> T {
         let faces = self.faces();
         let a = faces[0];
         let b = faces[1];
@@ -71,6 +134,74 @@ fn test_volume() {
     let line_ab = LineSegment { vertices: [a, b] };
     let line_ba = LineSegment { vertices: [b, a] };
 
-    assert!((line_ab.volume() - 6.7082039325).abs() <= f32::EPSILON);
-    assert!((line_ba.volume() - 6.7082039325).abs() <= f32::EPSILON);
+    assert!((line_ab.volume() - 6.708_204).abs() <= f32::EPSILON);
+    assert!((line_ba.volume() - 6.708_204).abs() <= f32::EPSILON);
+}
+
+#[cfg(test)]
+fn segment(a: [f64; 2], b: [f64; 2]) -> LineSegment<f64> {
+    LineSegment {
+        vertices: [Point { x: a[0], y: a[1] }, Point { x: b[0], y: b[1] }],
+    }
+}
+
+#[test]
+fn test_intersection_crossing() {
+    let s = segment([0.0, 0.0], [4.0, 4.0]);
+    let t = segment([0.0, 4.0], [4.0, 0.0]);
+    let expected = SegmentIntersection::Point(Point { x: 2.0, y: 2.0 });
+    assert_eq!(s.intersection_point(&t), expected);
+    assert_eq!(t.intersection_point(&s), expected);
+
+    // The lines through the segments cross, but away from the segments.
+    let t = segment([3.0, 0.0], [4.0, -1.0]);
+    assert_eq!(s.intersection_point(&t), SegmentIntersection::None);
+}
+
+#[test]
+fn test_intersection_touching() {
+    // A T-junction.
+    let s = segment([0.0, 0.0], [4.0, 0.0]);
+    let t = segment([1.0, 0.0], [1.0, 3.0]);
+    let expected = SegmentIntersection::Point(Point { x: 1.0, y: 0.0 });
+    assert_eq!(s.intersection_point(&t), expected);
+    assert_eq!(t.intersection_point(&s), expected);
+
+    // Shared endpoints, both collinear and not.
+    let t = segment([4.0, 0.0], [5.0, 2.0]);
+    let expected = SegmentIntersection::Point(Point { x: 4.0, y: 0.0 });
+    assert_eq!(s.intersection_point(&t), expected);
+    let t = segment([6.0, 0.0], [4.0, 0.0]);
+    assert_eq!(s.intersection_point(&t), expected);
+
+    // Almost touching.
+    let t = segment([1.0, 1e-300], [1.0, 3.0]);
+    assert_eq!(s.intersection_point(&t), SegmentIntersection::None);
+}
+
+#[test]
+fn test_intersection_collinear() {
+    let s = segment([0.0, 0.0], [2.0, 2.0]);
+    let t = segment([3.0, 3.0], [1.0, 1.0]);
+    let overlap = |a: [f64; 2], b: [f64; 2]| SegmentIntersection::Segment(segment(a, b));
+    assert_eq!(s.intersection_point(&t), overlap([1.0, 1.0], [2.0, 2.0]));
+    assert_eq!(t.intersection_point(&s), overlap([2.0, 2.0], [1.0, 1.0]));
+
+    // One segment inside the other.
+    let t = segment([0.5, 0.5], [1.5, 1.5]);
+    assert_eq!(s.intersection_point(&t), overlap([0.5, 0.5], [1.5, 1.5]));
+
+    // On the same line, but apart.
+    let t = segment([3.0, 3.0], [4.0, 4.0]);
+    assert_eq!(s.intersection_point(&t), SegmentIntersection::None);
+
+    // Parallel.
+    let t = segment([0.0, 1.0], [2.0, 3.0]);
+    assert_eq!(s.intersection_point(&t), SegmentIntersection::None);
+
+    // A segment that is a single point.
+    let t = segment([1.0, 1.0], [1.0, 1.0]);
+    let expected = SegmentIntersection::Point(Point { x: 1.0, y: 1.0 });
+    assert_eq!(s.intersection_point(&t), expected);
+    assert_eq!(t.intersection_point(&s), expected);
 }
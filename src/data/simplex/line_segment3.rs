@@ -0,0 +1,60 @@
+use crate::data::{point::Point3, sphere::Sphere2, Scalar};
+
+use super::Simplex;
+
+/// A line segment in space, such as an edge of a `Triangle3`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineSegment3<T: Scalar = f32> {
+    pub vertices: [Point3<T>; 2],
+}
+
+impl<T: Scalar> Simplex<T> for LineSegment3<T> {
+    type Vertex = Point3<T>;
+    type Face = Point3<T>;
+    type S = Sphere2<T>;
+
+    fn dimension() -> u32 {
+        1
+    }
+
+    fn faces(&self) -> Vec<Self::Face> {
+        vec![self.vertices[0], self.vertices[1]]
+    }
+
+    fn vertices(&self) -> &[Point3<T>] {
+        &self.vertices
+    }
+
+    fn volume(&self) -> T {
+        let [a, b] = self.vertices;
+        let [x, y, z] = [b.x - a.x, b.y - a.y, b.z - a.z];
+        (x * x + y * y + z * z).sqrt()
+    }
+
+    /// The smallest sphere through both ends, centered halfway between them.
+    fn circumscribe(&self) -> Option<Self::S> {
+        let [a, b] = self.vertices;
+        let half = |p: T, q: T| (p + q) / T::from_f64(2.0);
+        let center = Point3 {
+            x: half(a.x, b.x),
+            y: half(a.y, b.y),
+            z: half(a.z, b.z),
+        };
+        Some(Sphere2 {
+            center,
+            radius: self.volume() / T::from_f64(2.0),
+        })
+    }
+}
+
+#[cfg(test)]
+use crate::data::point::point3;
+
+#[test]
+fn test_volume() {
+    let line = LineSegment3 {
+        vertices: [point3(1.0, 2.0, 3.0), point3(3.0, 5.0, 9.0)],
+    };
+    assert_eq!(line.volume(), 7.0);
+    assert_eq!(line.circumscribe().unwrap().center, point3(2.0, 3.5, 6.0));
+}
@@ -1,11 +1,17 @@
 pub mod line_segment;
+pub mod line_segment3;
 pub mod point;
+#[allow(clippy::module_inception)]
 pub mod simplex;
 pub mod tetrahedron;
 pub mod triangle;
+pub mod triangle3;
 
-pub use line_segment::LineSegment;
+pub use line_segment::{LineSegment, SegmentIntersection};
+pub use line_segment3::LineSegment3;
+pub(crate) use point::Lexicographic;
 pub use point::Point;
 pub use simplex::Simplex;
 pub use tetrahedron::Tetrahedron;
 pub use triangle::Triangle;
+pub use triangle3::Triangle3;
@@ -1,18 +1,54 @@
-use std::ops::{Add, Mul};
+use std::{
+    cmp::Ordering,
+    ops::{Add, Mul, Sub},
+};
 
-use crate::data::sphere::Sphere1;
+use crate::data::{sphere::Sphere1, Pos2, Scalar, Vec2};
 
 use super::simplex::Simplex;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Point {
-    pub x: f32,
-    pub y: f32,
+pub struct Point<T: Scalar = f32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Simplex for Point {
-    type Face = Point;
-    type S = Sphere1;
+impl<T: Scalar> Point<T> {
+    /// Orders points by x and then by y, with -0.0 the same as 0.0.
+    pub fn lexicographic(&self, other: &Self) -> Ordering {
+        let x = self.x.numeric_cmp(&other.x);
+        x.then_with(|| self.y.numeric_cmp(&other.y))
+    }
+}
+
+/// A point ordered by [`Point::lexicographic`], to key ordered maps with.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Lexicographic<T: Scalar>(pub Point<T>);
+
+impl<T: Scalar> PartialEq for Lexicographic<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Scalar> Eq for Lexicographic<T> {}
+
+impl<T: Scalar> PartialOrd for Lexicographic<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Scalar> Ord for Lexicographic<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.lexicographic(&other.0)
+    }
+}
+
+impl<T: Scalar> Simplex<T> for Point<T> {
+    type Vertex = Point<T>;
+    type Face = Point<T>;
+    type S = Sphere1<T>;
 
     fn dimension() -> u32 {
         0
@@ -22,12 +58,12 @@ impl Simplex for Point {
         vec![]
     }
 
-    fn vertices(&self) -> &[Point] {
+    fn vertices(&self) -> &[Point<T>] {
         &[]
     }
 
-    fn volume(&self) -> f32 {
-        0.0
+    fn volume(&self) -> T {
+        T::zero()
     }
 
     fn circumscribe(&self) -> Option<Self::S> {
@@ -36,7 +72,7 @@ impl Simplex for Point {
     }
 }
 
-impl Add for Point {
+impl<T: Scalar> Add for Point<T> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
         Self {
@@ -46,7 +82,17 @@ impl Add for Point {
     }
 }
 
-impl Mul for Point {
+impl<T: Scalar> Sub for Point<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: Scalar> Mul for Point<T> {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
         Self {
@@ -55,3 +101,23 @@ impl Mul for Point {
         }
     }
 }
+
+impl<T: Scalar> From<Point<T>> for Vec2<T> {
+    fn from(point: Point<T>) -> Self {
+        let Point { x, y } = point;
+        Vec2 { x, y }
+    }
+}
+
+impl<T: Scalar> From<Point<T>> for [T; 2] {
+    fn from(point: Point<T>) -> Self {
+        [point.x, point.y]
+    }
+}
+
+impl<T: Scalar> From<Pos2<T>> for Point<T> {
+    fn from(pos: Pos2<T>) -> Self {
+        let Pos2 { x, y } = pos;
+        Point { x, y }
+    }
+}
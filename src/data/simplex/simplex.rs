@@ -1,14 +1,13 @@
-use crate::data::sphere::Sphere;
+use crate::data::{sphere::Sphere, Scalar};
 
-use super::Point;
-
-pub trait Simplex {
-    type Face: Simplex;
-    type S: Sphere;
+pub trait Simplex<T: Scalar = f32> {
+    type Vertex;
+    type Face: Simplex<T>;
+    type S: Sphere<T>;
 
     fn dimension() -> u32;
     fn faces(&self) -> Vec<Self::Face>;
-    fn vertices(&self) -> &[Point];
-    fn volume(&self) -> f32;
+    fn vertices(&self) -> &[Self::Vertex];
+    fn volume(&self) -> T;
     fn circumscribe(&self) -> Option<Self::S>;
 }
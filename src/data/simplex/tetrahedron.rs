@@ -1,17 +1,56 @@
-use crate::data::sphere::Sphere2;
+use std::cmp::Ordering;
 
-use super::{simplex::Simplex, triangle::Triangle, Point};
+use crate::data::{point::Point3, sphere::Sphere2, Scalar};
 
-pub struct Tetrahedron {
-    pub vertices: [Point; 4],
+use super::{simplex::Simplex, triangle3::Triangle3};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tetrahedron<T: Scalar = f32> {
+    pub vertices: [Point3<T>; 4],
 }
 
-impl Simplex for Tetrahedron {
-    type Face = Triangle;
-    type S = Sphere2;
+impl<T: Scalar> Tetrahedron<T> {
+    /// Whether the vertices lie on a common plane, so the tetrahedron has no volume.
+    pub fn is_degenerate(&self) -> bool {
+        self.orientation() == Ordering::Equal
+    }
+
+    /// The sign of the volume, computed exactly.
+    fn orientation(&self) -> Ordering {
+        let [a, b, c, d] = self.vertices.map(<[T; 3]>::from);
+        // `orient3d` is positive when `d` is on the other side.
+        T::orient3d(a, b, c, d).reverse()
+    }
+}
+
+impl<T: Scalar> Simplex<T> for Tetrahedron<T> {
+    type Vertex = Point3<T>;
+    /// Faces go counter-clockwise as seen from outside, like the faces of a `Mesh`.
+    type Face = Triangle3<T>;
+    type S = Sphere2<T>;
 
     fn circumscribe(&self) -> Option<Self::S> {
-        todo!()
+        // https://en.wikipedia.org/wiki/Tetrahedron#Circumcenter
+        // Working relative to `a` keeps the squared terms small, as for `Triangle`.
+        if self.is_degenerate() {
+            return None;
+        }
+        let [a, b, c, d] = self.vertices;
+        let [b, c, d] = [b, c, d].map(|p| [p.x - a.x, p.y - a.y, p.z - a.z]);
+        let [b2, c2, d2] = [b, c, d].map(|p| dot(p, p));
+        let cd = cross(c, d);
+        let db = cross(d, b);
+        let bc = cross(b, c);
+        let denominator = T::from_f64(2.0) * dot(b, cd);
+        let [ux, uy, uz] = [0, 1, 2].map(|i| (b2 * cd[i] + c2 * db[i] + d2 * bc[i]) / denominator);
+        let center = Point3 {
+            x: a.x + ux,
+            y: a.y + uy,
+            z: a.z + uz,
+        };
+        let radius = (ux * ux + uy * uy + uz * uz).sqrt();
+        // Nearly flat tetrahedra can still overflow.
+        radius.is_finite().then_some(Sphere2 { center, radius })
     }
 
     fn dimension() -> u32 {
@@ -19,27 +58,124 @@ impl Simplex for Tetrahedron {
     }
 
     fn faces(&self) -> Vec<Self::Face> {
-        vec![
-            Triangle {
-                vertices: [self.vertices[0], self.vertices[1], self.vertices[2]],
-            },
-            Triangle {
-                vertices: [self.vertices[0], self.vertices[1], self.vertices[3]],
-            },
-            Triangle {
-                vertices: [self.vertices[0], self.vertices[2], self.vertices[3]],
-            },
-            Triangle {
-                vertices: [self.vertices[1], self.vertices[2], self.vertices[3]],
-            },
-        ]
-    }
-
-    fn vertices(&self) -> &[Point] {
+        let [a, b, c, d] = self.vertices;
+        let faces = [[b, c, d], [a, d, c], [a, b, d], [a, c, b]];
+        let reversed = self.orientation() == Ordering::Less;
+        faces
+            .into_iter()
+            .map(|[a, b, c]| Triangle3 {
+                vertices: if reversed { [a, c, b] } else { [a, b, c] },
+            })
+            .collect()
+    }
+
+    fn vertices(&self) -> &[Point3<T>] {
         &self.vertices
     }
 
-    fn volume(&self) -> f32 {
-        unimplemented!()
+    /// The signed volume, which is positive when `a`, `b` and `c` appear
+    /// counter-clockwise as seen from `d`.
+    fn volume(&self) -> T {
+        let [a, b, c, d] = self.vertices;
+        let [b, c, d] = [b, c, d].map(|p| [p.x - a.x, p.y - a.y, p.z - a.z]);
+        dot(b, cross(c, d)) / T::from_f64(6.0)
+    }
+}
+
+pub(super) fn dot<T: Scalar>(u: [T; 3], v: [T; 3]) -> T {
+    u[0] * v[0] + u[1] * v[1] + u[2] * v[2]
+}
+
+pub(super) fn cross<T: Scalar>(u: [T; 3], v: [T; 3]) -> [T; 3] {
+    [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ]
+}
+
+#[cfg(test)]
+use num_rational::Rational64;
+
+#[cfg(test)]
+use crate::data::{point::point3, sphere::Sphere};
+
+#[test]
+fn test_volume() {
+    let t = Tetrahedron {
+        vertices: [
+            point3(1.0, 1.0, 1.0),
+            point3(3.0, 1.0, 1.0),
+            point3(1.0, 4.0, 1.0),
+            point3(1.0, 1.0, 6.0),
+        ],
+    };
+    assert_eq!(t.volume(), 5.0);
+
+    let [a, b, c, d] = t.vertices;
+    let t = Tetrahedron {
+        vertices: [a, c, b, d],
+    };
+    assert_eq!(t.volume(), -5.0);
+}
+
+#[test]
+fn test_faces() {
+    for vertices in [
+        [[0, 0, 0], [1, 0, 0], [0, 1, 0], [0, 0, 1]],
+        [[0, 0, 0], [0, 1, 0], [1, 0, 0], [0, 0, 1]],
+    ] {
+        let vertices = vertices.map(|[x, y, z]| point3(x as f64, y as f64, z as f64));
+        let t = Tetrahedron { vertices };
+        let faces = t.faces();
+        assert_eq!(faces.len(), 4);
+        // The faces are opposite the vertices in turn, which lie below them.
+        for (face, d) in faces.into_iter().zip(vertices) {
+            let [a, b, c] = face.vertices;
+            let orientation = f64::orient3d(a.into(), b.into(), c.into(), d.into());
+            assert_eq!(orientation, Ordering::Greater);
+        }
+    }
+}
+
+#[test]
+fn test_circumscribe() {
+    let vertices = [[1, 2, 3], [5, 2, 3], [1, 7, 3], [1, 2, -4]];
+    let t = Tetrahedron {
+        vertices: vertices.map(|[x, y, z]| point3(x as f64, y as f64, z as f64)),
+    };
+    let sphere = t.circumscribe().unwrap();
+    assert_eq!(sphere.center(), point3(3.0, 4.5, -0.5));
+    for p in t.vertices {
+        let distance = ((p.x - 3.0).powi(2) + (p.y - 4.5).powi(2) + (p.z + 0.5).powi(2)).sqrt();
+        assert!((distance - sphere.radius()).abs() < 1e-12);
     }
+    assert!(sphere.contains(point3(2.0, 3.0, 0.0)));
+    assert!(!sphere.contains(point3(10.0, 3.0, 0.0)));
+
+    // Exactly, in rationals.
+    let t = Tetrahedron {
+        vertices: vertices.map(|p| {
+            let [x, y, z] = p.map(Rational64::from_integer);
+            point3(x, y, z)
+        }),
+    };
+    let center = t.circumscribe().unwrap().center;
+    let half = |n| Rational64::new(n, 2);
+    assert_eq!(center, point3(half(6), half(9), half(-1)));
+}
+
+#[test]
+fn test_degenerate() {
+    let t = Tetrahedron {
+        vertices: [
+            point3(0.0, 0.0, 1.0),
+            point3(1.0, 0.0, 1.0),
+            point3(0.0, 1.0, 1.0),
+            point3(1.0, 1.0, 1.0),
+        ],
+    };
+    assert!(t.is_degenerate());
+    assert_eq!(t.volume(), 0.0);
+    assert!(t.circumscribe().is_none());
 }
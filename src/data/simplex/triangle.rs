@@ -1,13 +1,15 @@
-use crate::data::{point::Point2, sphere::Sphere1};
+use std::cmp::Ordering;
+
+use crate::data::{point::Point2, sphere::Sphere1, Scalar};
 
 use super::{line_segment::LineSegment, simplex::Simplex, Point};
 
-#[derive(Debug)]
-pub struct Triangle {
-    pub vertices: [Point; 3],
+#[derive(Debug, Clone, Copy)]
+pub struct Triangle<T: Scalar = f32> {
+    pub vertices: [Point<T>; 3],
 }
 
-impl PartialEq for Triangle {
+impl<T: Scalar> PartialEq for Triangle<T> {
     // TODO: should we consider any rotation of the triangle?
     fn eq(&self, other: &Self) -> bool {
         let [a1, b1, c1] = self.vertices;
@@ -16,29 +18,34 @@ impl PartialEq for Triangle {
     }
 }
 
-impl Simplex for Triangle {
-    type Face = LineSegment;
-    type S = Sphere1;
+impl<T: Scalar> Simplex<T> for Triangle<T> {
+    type Vertex = Point<T>;
+    type Face = LineSegment<T>;
+    type S = Sphere1<T>;
 
     fn circumscribe(&self) -> Option<Self::S> {
-        // https://en.wikipedia.org/wiki/Circumcircle#Circumcenter_coordinates
+        // https://en.wikipedia.org/wiki/Circumcircle#Cartesian_coordinates_2
+        // Working relative to `a` keeps the squared terms small, which matters a lot for
+        // f32 precision once the triangle is small compared to its distance from the
+        // origin.
         let [a, b, c] = self.vertices;
-        let Point { x: ax, y: ay } = a;
-        let Point { x: bx, y: by } = b;
-        let Point { x: cx, y: cy } = c;
-        let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
-        let ux = ((ax * ax + ay * ay) * (by - cy)
-            + (bx * bx + by * by) * (cy - ay)
-            + (cx * cx + cy * cy) * (ay - by))
-            / d;
-        let uy = ((ax * ax + ay * ay) * (cx - bx)
-            + (bx * bx + by * by) * (ax - cx)
-            + (cx * cx + cy * cy) * (bx - ax))
-            / d;
-        let center = Point2 { x: ux, y: uy };
-        // TODO: replace this with a faster method
-        let radius = ((ux - a.x).powf(2.0) + (uy - a.y).powf(2.0)).sqrt();
-        Some(Sphere1 { radius, center })
+        if T::orient2d(a.into(), b.into(), c.into()) == Ordering::Equal {
+            return None;
+        }
+        let Point { x: bx, y: by } = b - a;
+        let Point { x: cx, y: cy } = c - a;
+        let d = T::from_f64(2.0) * (bx * cy - by * cx);
+        let b2 = bx * bx + by * by;
+        let c2 = cx * cx + cy * cy;
+        let ux = (cy * b2 - by * c2) / d;
+        let uy = (bx * c2 - cx * b2) / d;
+        let center = Point2 {
+            x: a.x + ux,
+            y: a.y + uy,
+        };
+        let radius = ux.hypot(uy);
+        // Nearly collinear triangles can still overflow.
+        radius.is_finite().then_some(Sphere1 { radius, center })
     }
 
     fn dimension() -> u32 {
@@ -59,20 +66,36 @@ impl Simplex for Triangle {
         ]
     }
 
-    fn vertices(&self) -> &[Point] {
+    fn vertices(&self) -> &[Point<T>] {
         &self.vertices
     }
 
-    fn volume(&self) -> f32 {
+    fn volume(&self) -> T {
         let faces = self.faces();
         let a = faces[0].volume();
         let b = faces[1].volume();
         let c = faces[2].volume();
-        let s = (a + b + c) / 2.0;
+        let s = (a + b + c) / T::from_f64(2.0);
         (s * (s - a) * (s - b) * (s - c)).sqrt()
     }
 }
 
+impl<T: Scalar> Triangle<T> {
+    /// The smallest of the three angles of the triangle, in radians.
+    pub fn min_angle(&self) -> f64 {
+        let [a, b, c] = self.vertices.map(|p| [p.x.to_f64(), p.y.to_f64()]);
+        [[a, b, c], [b, c, a], [c, a, b]]
+            .map(|[p, q, r]| {
+                let u = [q[0] - p[0], q[1] - p[1]];
+                let v = [r[0] - p[0], r[1] - p[1]];
+                let cross = u[0] * v[1] - u[1] * v[0];
+                cross.abs().atan2(u[0] * v[0] + u[1] * v[1])
+            })
+            .into_iter()
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
 #[test]
 fn test_volume() {
     let a = Point { x: 1.0, y: 3.0 };
@@ -90,3 +113,24 @@ fn test_volume() {
         "Expected {expected} got {actual}"
     );
 }
+
+#[test]
+fn test_min_angle() {
+    let t = Triangle {
+        vertices: [
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 0.0, y: 2.0 },
+        ],
+    };
+    assert!((t.min_angle() - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+
+    let sliver = Triangle {
+        vertices: [
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+        ],
+    };
+    assert_eq!(sliver.min_angle(), 0.0);
+}
@@ -0,0 +1,110 @@
+use crate::data::{point::Point3, sphere::Sphere2, Scalar};
+
+use super::{
+    line_segment3::LineSegment3,
+    simplex::Simplex,
+    tetrahedron::{cross, dot},
+};
+
+/// A triangle in space, such as a face of a `Tetrahedron`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle3<T: Scalar = f32> {
+    pub vertices: [Point3<T>; 3],
+}
+
+impl<T: Scalar> Simplex<T> for Triangle3<T> {
+    type Vertex = Point3<T>;
+    type Face = LineSegment3<T>;
+    type S = Sphere2<T>;
+
+    /// The smallest sphere through the vertices, centered on the circumcenter in the
+    /// plane of the triangle.
+    fn circumscribe(&self) -> Option<Self::S> {
+        // https://en.wikipedia.org/wiki/Circumcircle#Higher_dimensions
+        // Working relative to `a`, as for `Triangle`.
+        let [a, b, c] = self.vertices;
+        let [b, c] = [b, c].map(|p| [p.x - a.x, p.y - a.y, p.z - a.z]);
+        let normal = cross(b, c);
+        let denominator = T::from_f64(2.0) * dot(normal, normal);
+        if denominator == T::zero() {
+            return None;
+        }
+        let [b2, c2] = [dot(b, b), dot(c, c)];
+        let towards = [0, 1, 2].map(|i| b2 * c[i] - c2 * b[i]);
+        let [ux, uy, uz] = cross(towards, normal).map(|u| u / denominator);
+        let center = Point3 {
+            x: a.x + ux,
+            y: a.y + uy,
+            z: a.z + uz,
+        };
+        let radius = (ux * ux + uy * uy + uz * uz).sqrt();
+        // Nearly collinear triangles can still overflow.
+        radius.is_finite().then_some(Sphere2 { center, radius })
+    }
+
+    fn dimension() -> u32 {
+        2
+    }
+
+    fn faces(&self) -> Vec<Self::Face> {
+        let [a, b, c] = self.vertices;
+        vec![
+            LineSegment3 { vertices: [a, b] },
+            LineSegment3 { vertices: [b, c] },
+            LineSegment3 { vertices: [a, c] },
+        ]
+    }
+
+    fn vertices(&self) -> &[Point3<T>] {
+        &self.vertices
+    }
+
+    fn volume(&self) -> T {
+        let [a, b, c] = self.vertices;
+        let [b, c] = [b, c].map(|p| [p.x - a.x, p.y - a.y, p.z - a.z]);
+        let normal = cross(b, c);
+        dot(normal, normal).sqrt() / T::from_f64(2.0)
+    }
+}
+
+#[cfg(test)]
+use num_rational::Rational64;
+
+#[cfg(test)]
+use crate::data::point::point3;
+
+#[test]
+fn test_volume() {
+    let t = Triangle3 {
+        vertices: [
+            point3(1.0, 1.0, 1.0),
+            point3(4.0, 1.0, 1.0),
+            point3(1.0, 1.0, 5.0),
+        ],
+    };
+    assert_eq!(t.volume(), 6.0);
+    assert_eq!(t.faces().iter().map(|f| f.volume()).sum::<f64>(), 12.0);
+}
+
+#[test]
+fn test_circumscribe() {
+    // A right triangle, whose circumcenter is halfway along its hypotenuse.
+    let t = Triangle3 {
+        vertices: [[1, 2, 3], [1, 6, 3], [1, 2, 9]].map(|p| {
+            let [x, y, z] = p.map(Rational64::from_integer);
+            point3(x, y, z)
+        }),
+    };
+    let sphere = t.circumscribe().unwrap();
+    let [one, four, six] = [1, 4, 6].map(Rational64::from_integer);
+    assert_eq!(sphere.center, point3(one, four, six));
+
+    let collinear = Triangle3 {
+        vertices: [
+            point3(0.0, 0.0, 0.0),
+            point3(1.0, 1.0, 1.0),
+            point3(2.0, 2.0, 2.0),
+        ],
+    };
+    assert!(collinear.circumscribe().is_none());
+}
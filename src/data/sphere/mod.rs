@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod sphere;
 pub mod sphere1;
 pub mod sphere2;
@@ -1,9 +1,9 @@
-use crate::data::point::point::Point;
+use crate::data::{point::point::Point, Scalar};
 
-pub trait Sphere {
+pub trait Sphere<T: Scalar = f32> {
     type P: Point;
 
-    fn area(&self) -> f32;
+    fn area(&self) -> T;
     fn center(&self) -> Self::P;
-    fn radius(&self) -> f32;
+    fn radius(&self) -> T;
 }
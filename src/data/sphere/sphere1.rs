@@ -1,28 +1,37 @@
-use core::f32;
+use std::f64::consts::PI;
 
-use crate::data::point::Point2;
+use crate::data::{point::Point2, Scalar};
 
 use super::Sphere;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 /// 2d circle
-pub struct Sphere1 {
-    pub radius: f32,
-    pub center: Point2,
+pub struct Sphere1<T: Scalar = f32> {
+    pub radius: T,
+    pub center: Point2<T>,
 }
 
-impl Sphere for Sphere1 {
-    type P = Point2;
+impl<T: Scalar> Sphere<T> for Sphere1<T> {
+    type P = Point2<T>;
 
-    fn area(&self) -> f32 {
-        f32::consts::PI * self.radius.powf(2.0)
+    fn area(&self) -> T {
+        T::from_f64(PI) * self.radius * self.radius
     }
 
     fn center(&self) -> Self::P {
         self.center
     }
 
-    fn radius(&self) -> f32 {
+    fn radius(&self) -> T {
         self.radius
     }
 }
+
+impl<T: Scalar> Sphere1<T> {
+    /// Whether `p` lies strictly inside the circle.
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        let dx = p.x - self.center.x;
+        let dy = p.y - self.center.y;
+        dx * dx + dy * dy < self.radius * self.radius
+    }
+}
@@ -1,27 +1,38 @@
-use core::f32;
+use std::f64::consts::PI;
 
-use crate::data::point::point3::Point3;
+use crate::data::{point::point3::Point3, Scalar};
 
 use super::Sphere;
 
+#[derive(Debug, PartialEq, Clone, Copy)]
 /// 3d Sphere
-pub struct Sphere2 {
-    center: Point3,
-    radius: f32,
+pub struct Sphere2<T: Scalar = f32> {
+    pub center: Point3<T>,
+    pub radius: T,
 }
 
-impl Sphere for Sphere2 {
-    type P = Point3;
+impl<T: Scalar> Sphere<T> for Sphere2<T> {
+    type P = Point3<T>;
 
-    fn area(&self) -> f32 {
-        f32::consts::PI * self.radius.powf(2.0) * 4.0
+    fn area(&self) -> T {
+        T::from_f64(PI * 4.0) * self.radius * self.radius
     }
 
     fn center(&self) -> Self::P {
         self.center
     }
 
-    fn radius(&self) -> f32 {
+    fn radius(&self) -> T {
         self.radius
     }
 }
+
+impl<T: Scalar> Sphere2<T> {
+    /// Whether `p` lies strictly inside the sphere.
+    pub fn contains(&self, p: Point3<T>) -> bool {
+        let dx = p.x - self.center.x;
+        let dy = p.y - self.center.y;
+        let dz = p.z - self.center.z;
+        dx * dx + dy * dy + dz * dz < self.radius * self.radius
+    }
+}
@@ -1,26 +1,34 @@
 use std::ops::{Div, Sub};
 
+use super::Scalar;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
-pub struct Vec2 {
-    pub x: f32,
-    pub y: f32,
+pub struct Vec2<T: Scalar = f32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl std::fmt::Display for Vec2 {
+impl<T: Scalar> std::fmt::Display for Vec2<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
 
-pub fn vec2(x: f32, y: f32) -> Vec2 {
+pub fn vec2<T: Scalar>(x: T, y: T) -> Vec2<T> {
     Vec2 { x, y }
 }
 
-impl Vec2 {
+impl<T: Scalar> From<Vec2<T>> for [T; 2] {
+    fn from(v: Vec2<T>) -> Self {
+        [v.x, v.y]
+    }
+}
+
+impl<T: Scalar> Vec2<T> {
     // returns the magnitude of the vector that would result from a regular 3D cross
     // product of the input vectors, taking their Z values implicitly as 0
     #[inline]
-    pub fn cross(self, other: Self) -> f32 {
+    pub fn cross(self, other: Self) -> T {
         let Self { x: x0, y: y0 } = self; // b
         let Self { x: x1, y: y1 } = other; // c
         (x0 * y1) - (y0 * x1)
@@ -28,12 +36,12 @@ impl Vec2 {
 
     /// The dot-product of two vectors.
     #[inline]
-    pub fn dot(self, other: Self) -> f32 {
+    pub fn dot(self, other: Self) -> T {
         self.x * other.x + self.y * other.y
     }
 
     #[inline(always)]
-    pub fn length(self) -> f32 {
+    pub fn length(self) -> T {
         self.x.hypot(self.y)
     }
 
@@ -51,7 +59,7 @@ impl Vec2 {
     pub fn normalize(self) -> Self {
         //
         let len = self.length();
-        if len <= 0.0 {
+        if len <= T::zero() {
             self
         } else {
             self / len
@@ -72,7 +80,7 @@ impl Vec2 {
 }
 
 /// Element-wise division
-impl Div<Self> for Vec2 {
+impl<T: Scalar> Div<Self> for Vec2<T> {
     type Output = Self;
 
     #[inline(always)]
@@ -84,11 +92,11 @@ impl Div<Self> for Vec2 {
     }
 }
 
-impl Div<f32> for Vec2 {
+impl<T: Scalar> Div<T> for Vec2<T> {
     type Output = Self;
 
     #[inline(always)]
-    fn div(self, factor: f32) -> Self {
+    fn div(self, factor: T) -> Self {
         Self {
             x: self.x / factor,
             y: self.y / factor,
@@ -96,10 +104,10 @@ impl Div<f32> for Vec2 {
     }
 }
 
-impl Sub<Vec2> for Vec2 {
+impl<T: Scalar> Sub<Vec2<T>> for Vec2<T> {
     type Output = Self;
 
-    fn sub(self, rhs: Vec2) -> Self::Output {
+    fn sub(self, rhs: Vec2<T>) -> Self::Output {
         Self {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
@@ -0,0 +1,202 @@
+use super::{simplex::Point, Scalar, Vec2};
+
+/// A Voronoi diagram, made up of the edges between the cells of neighbouring sites.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Voronoi<T: Scalar = f32> {
+    pub sites: Vec<Point<T>>,
+    pub vertices: Vec<Point<T>>,
+    pub edges: Vec<VoronoiEdge>,
+}
+
+/// An edge of a Voronoi diagram, on the bisector of the two sites it separates. It
+/// runs from `vertices[0]` to `vertices[1]`, with `sites[0]` to its left. A missing
+/// vertex means that end of the edge lies at infinity, making the edge a ray (or a
+/// line, when both are missing).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoronoiEdge {
+    pub sites: [usize; 2],
+    pub vertices: [Option<usize>; 2],
+}
+
+impl<T: Scalar> Voronoi<T> {
+    /// The direction in which `edge` runs, which is perpendicular to its sites.
+    pub fn direction(&self, edge: &VoronoiEdge) -> Vec2<T> {
+        let [left, right] = edge.sites.map(|i| self.sites[i]);
+        let Point { x, y } = right - left;
+        Vec2 { x, y }.perpendicular()
+    }
+
+    /// The part of `edge` inside the box from `min` to `max`, if any.
+    pub fn clip_edge(
+        &self,
+        edge: &VoronoiEdge,
+        min: Point<T>,
+        max: Point<T>,
+    ) -> Option<[Point<T>; 2]> {
+        let direction = self.direction(edge);
+        let direction = Point {
+            x: direction.x,
+            y: direction.y,
+        };
+        // The edge as the points origin + t * direction, for t in [start, end].
+        let (origin, direction, start, end) =
+            match edge.vertices.map(|v| v.map(|v| self.vertices[v])) {
+                [Some(a), Some(b)] => (a, b - a, Some(T::zero()), Some(T::one())),
+                [Some(a), None] => (a, direction, Some(T::zero()), None),
+                [None, Some(b)] => (b, direction, None, Some(T::zero())),
+                [None, None] => {
+                    let [a, b] = edge.sites.map(|i| self.sites[i]);
+                    let two = T::from_f64(2.0);
+                    let midpoint = Point {
+                        x: (a.x + b.x) / two,
+                        y: (a.y + b.y) / two,
+                    };
+                    (midpoint, direction, None, None)
+                }
+            };
+
+        // https://en.wikipedia.org/wiki/Liang%E2%80%93Barsky_algorithm
+        let (mut start, mut end) = (start, end);
+        for (p, q) in [
+            (-direction.x, origin.x - min.x),
+            (direction.x, max.x - origin.x),
+            (-direction.y, origin.y - min.y),
+            (direction.y, max.y - origin.y),
+        ] {
+            if p == T::zero() {
+                // Parallel to this side of the box, and either entirely inside or outside.
+                if q < T::zero() {
+                    return None;
+                }
+                continue;
+            }
+            let t = q / p;
+            if p < T::zero() {
+                start = Some(start.map_or(t, |start| if t > start { t } else { start }));
+            } else {
+                end = Some(end.map_or(t, |end| if t < end { t } else { end }));
+            }
+        }
+        let (start, end) = (start?, end?);
+        (start <= end).then(|| {
+            [start, end].map(|t| Point {
+                x: origin.x + direction.x * t,
+                y: origin.y + direction.y * t,
+            })
+        })
+    }
+
+    /// The cells of the sites, clipped to the box from `min` to `max`, as
+    /// counter-clockwise polygons.
+    ///
+    /// Sites that are duplicates of another site are left without a cell.
+    pub fn cells(&self, min: Point<T>, max: Point<T>) -> Vec<Vec<Point<T>>> {
+        let mut neighbours = vec![vec![]; self.sites.len()];
+        for VoronoiEdge { sites: [a, b], .. } in &self.edges {
+            neighbours[*a].push(*b);
+            neighbours[*b].push(*a);
+        }
+
+        // A cell is the part of the box closer to its site than to any of its
+        // neighbours, so cut away the far side of the bisector of each of them.
+        let two = T::from_f64(2.0);
+        let square = vec![
+            min,
+            Point { x: max.x, y: min.y },
+            max,
+            Point { x: min.x, y: max.y },
+        ];
+        neighbours
+            .into_iter()
+            .enumerate()
+            .map(|(site, neighbours)| {
+                if neighbours.is_empty() && self.sites.len() > 1 {
+                    return vec![];
+                }
+                let p = self.sites[site];
+                neighbours
+                    .into_iter()
+                    .fold(square.clone(), |polygon, neighbour| {
+                        let q = self.sites[neighbour];
+                        let midpoint = Point {
+                            x: (p.x + q.x) / two,
+                            y: (p.y + q.y) / two,
+                        };
+                        // Positive on the side of the neighbour.
+                        clip_polygon(&polygon, |r| {
+                            (r.x - midpoint.x) * (q.x - p.x) + (r.y - midpoint.y) * (q.y - p.y)
+                        })
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Cuts away the part of the convex `polygon` where `side` is positive, which has to be
+/// an affine function.
+fn clip_polygon<T: Scalar>(polygon: &[Point<T>], side: impl Fn(Point<T>) -> T) -> Vec<Point<T>> {
+    let mut clipped = vec![];
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let (side_a, side_b) = (side(*a), side(b));
+        if side_a <= T::zero() {
+            clipped.push(*a);
+        }
+        if (side_a < T::zero() && side_b > T::zero()) || (side_a > T::zero() && side_b < T::zero())
+        {
+            let t = side_a / (side_a - side_b);
+            clipped.push(Point {
+                x: a.x + (b.x - a.x) * t,
+                y: a.y + (b.y - a.y) * t,
+            });
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point { x, y }
+    }
+
+    /// Two sites, separated by the line x = 0.
+    fn two_sites() -> Voronoi<f64> {
+        Voronoi {
+            sites: vec![point(-1.0, 0.0), point(1.0, 0.0)],
+            vertices: vec![],
+            edges: vec![VoronoiEdge {
+                sites: [1, 0],
+                vertices: [None, None],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_clip_edge() {
+        let voronoi = two_sites();
+        let edge = voronoi.edges[0];
+        assert_eq!(voronoi.direction(&edge), Vec2 { x: 0.0, y: -2.0 });
+        let clipped = voronoi.clip_edge(&edge, point(-2.0, -2.0), point(2.0, 2.0));
+        assert_eq!(clipped, Some([point(0.0, 2.0), point(0.0, -2.0)]));
+        let clipped = voronoi.clip_edge(&edge, point(1.0, -2.0), point(2.0, 2.0));
+        assert_eq!(clipped, None);
+    }
+
+    #[test]
+    fn test_cells() {
+        let voronoi = two_sites();
+        let cells = voronoi.cells(point(-2.0, -2.0), point(2.0, 2.0));
+        assert_eq!(
+            cells[0],
+            vec![
+                point(-2.0, -2.0),
+                point(0.0, -2.0),
+                point(0.0, 2.0),
+                point(-2.0, 2.0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod algorithms;
+pub mod color;
+pub mod data;
+pub mod predicates;
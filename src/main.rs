@@ -1,9 +1,6 @@
 use eframe::egui;
 
-pub mod algorithms;
 mod app;
-pub mod color;
-pub mod data;
 
 fn main() -> eframe::Result {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
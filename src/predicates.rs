@@ -0,0 +1,560 @@
+//! Robust geometric predicates.
+//!
+//! Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates
+//! J. R. Shewchuk
+//! https://www.cs.cmu.edu/~quake/robust.html
+//!
+//! Each predicate first evaluates its determinant with plain floating point arithmetic
+//! and checks the result against a forward error bound. Only when the bound can't
+//! vouch for the sign (i.e. the points are nearly degenerate) is the determinant
+//! evaluated again, exactly, using floating point expansions. The sign of the returned
+//! value is therefore always correct, while its magnitude is only approximate.
+
+use std::cmp::Ordering;
+
+use crate::data::Scalar;
+
+// 2^-53, half an ulp of 1.0
+const EPSILON: f64 = f64::EPSILON * 0.5;
+const CCW_ERR_BOUND_A: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const O3D_ERR_BOUND_A: f64 = (7.0 + 56.0 * EPSILON) * EPSILON;
+const ICC_ERR_BOUND_A: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+const ISP_ERR_BOUND_A: f64 = (16.0 + 224.0 * EPSILON) * EPSILON;
+
+/// Returns a positive value if `a`, `b` and `c` occur in counter-clockwise order, a
+/// negative value if they occur in clockwise order and zero if they are collinear.
+pub fn orient2d(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let det_left = (a[0] - c[0]) * (b[1] - c[1]);
+    let det_right = (a[1] - c[1]) * (b[0] - c[0]);
+    let det = det_left - det_right;
+
+    // When both products have different signs no cancellation can happen.
+    let det_sum = if det_left > 0.0 {
+        if det_right <= 0.0 {
+            return det;
+        }
+        det_left + det_right
+    } else if det_left < 0.0 {
+        if det_right >= 0.0 {
+            return det;
+        }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    let err_bound = CCW_ERR_BOUND_A * det_sum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+
+    orient2d_exact(a, b, c)
+}
+
+fn orient2d_exact(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let acx = Expansion::diff(a[0], c[0]);
+    let acy = Expansion::diff(a[1], c[1]);
+    let bcx = Expansion::diff(b[0], c[0]);
+    let bcy = Expansion::diff(b[1], c[1]);
+    acx.mul(&bcy).sub(&acy.mul(&bcx)).estimate()
+}
+
+/// Returns a positive value if `d` lies below the plane through `a`, `b` and `c`, a
+/// negative value if it lies above it and zero if the four points are coplanar. Above
+/// is the side from which `a`, `b` and `c` appear in counter-clockwise order.
+pub fn orient3d(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]) -> f64 {
+    let [adx, ady, adz] = [a[0] - d[0], a[1] - d[1], a[2] - d[2]];
+    let [bdx, bdy, bdz] = [b[0] - d[0], b[1] - d[1], b[2] - d[2]];
+    let [cdx, cdy, cdz] = [c[0] - d[0], c[1] - d[1], c[2] - d[2]];
+
+    let bdx_cdy = bdx * cdy;
+    let cdx_bdy = cdx * bdy;
+    let cdx_ady = cdx * ady;
+    let adx_cdy = adx * cdy;
+    let adx_bdy = adx * bdy;
+    let bdx_ady = bdx * ady;
+
+    let det = adz * (bdx_cdy - cdx_bdy) + bdz * (cdx_ady - adx_cdy) + cdz * (adx_bdy - bdx_ady);
+
+    let permanent = (bdx_cdy.abs() + cdx_bdy.abs()) * adz.abs()
+        + (cdx_ady.abs() + adx_cdy.abs()) * bdz.abs()
+        + (adx_bdy.abs() + bdx_ady.abs()) * cdz.abs();
+    let err_bound = O3D_ERR_BOUND_A * permanent;
+    if det > err_bound || -det > err_bound {
+        return det;
+    }
+
+    orient3d_exact(a, b, c, d)
+}
+
+fn orient3d_exact(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]) -> f64 {
+    let [adx, ady, adz] = [0, 1, 2].map(|i| Expansion::diff(a[i], d[i]));
+    let [bdx, bdy, bdz] = [0, 1, 2].map(|i| Expansion::diff(b[i], d[i]));
+    let [cdx, cdy, cdz] = [0, 1, 2].map(|i| Expansion::diff(c[i], d[i]));
+
+    let bc = bdx.mul(&cdy).sub(&cdx.mul(&bdy));
+    let ca = cdx.mul(&ady).sub(&adx.mul(&cdy));
+    let ab = adx.mul(&bdy).sub(&bdx.mul(&ady));
+
+    adz.mul(&bc)
+        .add(&bdz.mul(&ca))
+        .add(&cdz.mul(&ab))
+        .estimate()
+}
+
+/// Returns a positive value if `d` lies inside the circle passing through `a`, `b` and
+/// `c`, a negative value if it lies outside and zero if the four points are cocircular.
+/// `a`, `b` and `c` must be in counter-clockwise order, or the sign is reversed.
+pub fn incircle(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> f64 {
+    let adx = a[0] - d[0];
+    let bdx = b[0] - d[0];
+    let cdx = c[0] - d[0];
+    let ady = a[1] - d[1];
+    let bdy = b[1] - d[1];
+    let cdy = c[1] - d[1];
+
+    let bdx_cdy = bdx * cdy;
+    let cdx_bdy = cdx * bdy;
+    let a_lift = adx * adx + ady * ady;
+
+    let cdx_ady = cdx * ady;
+    let adx_cdy = adx * cdy;
+    let b_lift = bdx * bdx + bdy * bdy;
+
+    let adx_bdy = adx * bdy;
+    let bdx_ady = bdx * ady;
+    let c_lift = cdx * cdx + cdy * cdy;
+
+    let det =
+        a_lift * (bdx_cdy - cdx_bdy) + b_lift * (cdx_ady - adx_cdy) + c_lift * (adx_bdy - bdx_ady);
+
+    let permanent = (bdx_cdy.abs() + cdx_bdy.abs()) * a_lift
+        + (cdx_ady.abs() + adx_cdy.abs()) * b_lift
+        + (adx_bdy.abs() + bdx_ady.abs()) * c_lift;
+    let err_bound = ICC_ERR_BOUND_A * permanent;
+    if det > err_bound || -det > err_bound {
+        return det;
+    }
+
+    incircle_exact(a, b, c, d)
+}
+
+fn incircle_exact(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> f64 {
+    let adx = Expansion::diff(a[0], d[0]);
+    let bdx = Expansion::diff(b[0], d[0]);
+    let cdx = Expansion::diff(c[0], d[0]);
+    let ady = Expansion::diff(a[1], d[1]);
+    let bdy = Expansion::diff(b[1], d[1]);
+    let cdy = Expansion::diff(c[1], d[1]);
+
+    let a_lift = adx.mul(&adx).add(&ady.mul(&ady));
+    let b_lift = bdx.mul(&bdx).add(&bdy.mul(&bdy));
+    let c_lift = cdx.mul(&cdx).add(&cdy.mul(&cdy));
+
+    let bc = bdx.mul(&cdy).sub(&cdx.mul(&bdy));
+    let ca = cdx.mul(&ady).sub(&adx.mul(&cdy));
+    let ab = adx.mul(&bdy).sub(&bdx.mul(&ady));
+
+    a_lift
+        .mul(&bc)
+        .add(&b_lift.mul(&ca))
+        .add(&c_lift.mul(&ab))
+        .estimate()
+}
+
+/// Like [`incircle`], but cocircular points are broken up with Simulation of
+/// Simplicity (Edelsbrunner & Mücke): every point is lifted by an infinitesimal amount
+/// onto the paraboloid, the lexicographically smallest point the most. The result is
+/// only `Equal` when `a`, `b` and `c` are collinear or some points coincide.
+///
+/// This is what makes the Delaunay triangulation of e.g. a grid unique, so that
+/// different algorithms (and different parts of the same algorithm) agree on it.
+pub fn incircle_sos<T: Scalar>(a: [T; 2], b: [T; 2], c: [T; 2], d: [T; 2]) -> Ordering {
+    let det = T::incircle(a, b, c, d);
+    if det != Ordering::Equal {
+        return det;
+    }
+
+    // Raising the lift of a point by δ changes the determinant by δ times its cofactor.
+    let mut cofactors = [
+        (a, T::orient2d(b, c, d)),
+        (b, T::orient2d(c, a, d)),
+        (c, T::orient2d(a, b, d)),
+        (d, T::orient2d(a, b, c).reverse()),
+    ];
+    cofactors.sort_by(|(p, _), (q, _)| lexicographic(p, q));
+    cofactors
+        .into_iter()
+        .map(|(_, cofactor)| cofactor)
+        .find(|cofactor| *cofactor != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+fn lexicographic<T: Scalar, const N: usize>(p: &[T; N], q: &[T; N]) -> Ordering {
+    p.iter()
+        .zip(q)
+        .map(|(a, b)| a.numeric_cmp(b))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Returns a positive value if `e` lies inside the sphere passing through `a`, `b`, `c`
+/// and `d`, a negative value if it lies outside and zero if the five points are
+/// cospherical. `d` must lie below the plane through `a`, `b` and `c` (see
+/// [`orient3d`]), or the sign is reversed.
+pub fn insphere(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3], e: [f64; 3]) -> f64 {
+    let [aex, aey, aez] = [a[0] - e[0], a[1] - e[1], a[2] - e[2]];
+    let [bex, bey, bez] = [b[0] - e[0], b[1] - e[1], b[2] - e[2]];
+    let [cex, cey, cez] = [c[0] - e[0], c[1] - e[1], c[2] - e[2]];
+    let [dex, dey, dez] = [d[0] - e[0], d[1] - e[1], d[2] - e[2]];
+
+    let [aex_bey, bex_aey] = [aex * bey, bex * aey];
+    let [bex_cey, cex_bey] = [bex * cey, cex * bey];
+    let [cex_dey, dex_cey] = [cex * dey, dex * cey];
+    let [dex_aey, aex_dey] = [dex * aey, aex * dey];
+    let [aex_cey, cex_aey] = [aex * cey, cex * aey];
+    let [bex_dey, dex_bey] = [bex * dey, dex * bey];
+
+    let ab = aex_bey - bex_aey;
+    let bc = bex_cey - cex_bey;
+    let cd = cex_dey - dex_cey;
+    let da = dex_aey - aex_dey;
+    let ac = aex_cey - cex_aey;
+    let bd = bex_dey - dex_bey;
+
+    let abc = aez * bc - bez * ac + cez * ab;
+    let bcd = bez * cd - cez * bd + dez * bc;
+    let cda = cez * da + dez * ac + aez * cd;
+    let dab = dez * ab + aez * bd + bez * da;
+
+    let a_lift = aex * aex + aey * aey + aez * aez;
+    let b_lift = bex * bex + bey * bey + bez * bez;
+    let c_lift = cex * cex + cey * cey + cez * cez;
+    let d_lift = dex * dex + dey * dey + dez * dez;
+
+    let det = (d_lift * abc - c_lift * dab) + (b_lift * cda - a_lift * bcd);
+
+    let [aez, bez, cez, dez] = [aez.abs(), bez.abs(), cez.abs(), dez.abs()];
+    let ab = aex_bey.abs() + bex_aey.abs();
+    let bc = bex_cey.abs() + cex_bey.abs();
+    let cd = cex_dey.abs() + dex_cey.abs();
+    let da = dex_aey.abs() + aex_dey.abs();
+    let ac = aex_cey.abs() + cex_aey.abs();
+    let bd = bex_dey.abs() + dex_bey.abs();
+    let permanent = (cd * bez + bd * cez + bc * dez) * a_lift
+        + (da * cez + ac * dez + cd * aez) * b_lift
+        + (ab * dez + bd * aez + da * bez) * c_lift
+        + (bc * aez + ac * bez + ab * cez) * d_lift;
+    let err_bound = ISP_ERR_BOUND_A * permanent;
+    if det > err_bound || -det > err_bound {
+        return det;
+    }
+
+    insphere_exact(a, b, c, d, e)
+}
+
+fn insphere_exact(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3], e: [f64; 3]) -> f64 {
+    let [aex, aey, aez] = [0, 1, 2].map(|i| Expansion::diff(a[i], e[i]));
+    let [bex, bey, bez] = [0, 1, 2].map(|i| Expansion::diff(b[i], e[i]));
+    let [cex, cey, cez] = [0, 1, 2].map(|i| Expansion::diff(c[i], e[i]));
+    let [dex, dey, dez] = [0, 1, 2].map(|i| Expansion::diff(d[i], e[i]));
+
+    let ab = aex.mul(&bey).sub(&bex.mul(&aey));
+    let bc = bex.mul(&cey).sub(&cex.mul(&bey));
+    let cd = cex.mul(&dey).sub(&dex.mul(&cey));
+    let da = dex.mul(&aey).sub(&aex.mul(&dey));
+    let ac = aex.mul(&cey).sub(&cex.mul(&aey));
+    let bd = bex.mul(&dey).sub(&dex.mul(&bey));
+
+    let abc = aez.mul(&bc).sub(&bez.mul(&ac)).add(&cez.mul(&ab));
+    let bcd = bez.mul(&cd).sub(&cez.mul(&bd)).add(&dez.mul(&bc));
+    let cda = cez.mul(&da).add(&dez.mul(&ac)).add(&aez.mul(&cd));
+    let dab = dez.mul(&ab).add(&aez.mul(&bd)).add(&bez.mul(&da));
+
+    let lift = |x: &Expansion, y: &Expansion, z: &Expansion| x.mul(x).add(&y.mul(y)).add(&z.mul(z));
+    let a_lift = lift(&aex, &aey, &aez);
+    let b_lift = lift(&bex, &bey, &bez);
+    let c_lift = lift(&cex, &cey, &cez);
+    let d_lift = lift(&dex, &dey, &dez);
+
+    d_lift
+        .mul(&abc)
+        .sub(&c_lift.mul(&dab))
+        .add(&b_lift.mul(&cda))
+        .sub(&a_lift.mul(&bcd))
+        .estimate()
+}
+
+/// Like [`insphere`], with cospherical points broken up in the same way as
+/// [`incircle_sos`]. The result is only `Equal` when `a`, `b`, `c` and `d` are coplanar
+/// or some points coincide.
+pub fn insphere_sos<T: Scalar>(a: [T; 3], b: [T; 3], c: [T; 3], d: [T; 3], e: [T; 3]) -> Ordering {
+    let det = T::insphere(a, b, c, d, e);
+    if det != Ordering::Equal {
+        return det;
+    }
+
+    let mut cofactors = [
+        (a, T::orient3d(b, c, d, e).reverse()),
+        (b, T::orient3d(c, d, a, e)),
+        (c, T::orient3d(d, a, b, e).reverse()),
+        (d, T::orient3d(a, b, c, e)),
+        (e, T::orient3d(a, b, c, d).reverse()),
+    ];
+    cofactors.sort_by(|(p, _), (q, _)| lexicographic(p, q));
+    cofactors
+        .into_iter()
+        .map(|(_, cofactor)| cofactor)
+        .find(|cofactor| *cofactor != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// A number represented exactly as the sum of non-overlapping floating point
+/// components, sorted by increasing magnitude. Zero components are dropped.
+#[derive(Debug, Clone)]
+struct Expansion(Vec<f64>);
+
+impl Expansion {
+    /// a - b, exactly
+    fn diff(a: f64, b: f64) -> Self {
+        let (x, y) = two_diff(a, b);
+        Self::from_pair(x, y)
+    }
+
+    fn from_pair(x: f64, y: f64) -> Self {
+        Self([y, x].into_iter().filter(|c| *c != 0.0).collect())
+    }
+
+    /// Adds a single component to the expansion (Shewchuk's GROW-EXPANSION).
+    fn grow(&self, b: f64) -> Self {
+        let mut q = b;
+        let mut h = Vec::with_capacity(self.0.len() + 1);
+        for e in &self.0 {
+            let (sum, err) = two_sum(q, *e);
+            if err != 0.0 {
+                h.push(err);
+            }
+            q = sum;
+        }
+        if q != 0.0 {
+            h.push(q);
+        }
+        Self(h)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        other.0.iter().fold(self.clone(), |sum, f| sum.grow(*f))
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        other.0.iter().fold(self.clone(), |sum, f| sum.grow(-f))
+    }
+
+    /// Multiplies the expansion by a single component (Shewchuk's SCALE-EXPANSION).
+    fn scale(&self, b: f64) -> Self {
+        let mut h = Vec::with_capacity(self.0.len() * 2);
+        let Some((first, rest)) = self.0.split_first() else {
+            return Self(h);
+        };
+        let (mut q, err) = two_product(*first, b);
+        if err != 0.0 {
+            h.push(err);
+        }
+        for e in rest {
+            let (product, product_err) = two_product(*e, b);
+            let (sum, err) = two_sum(q, product_err);
+            if err != 0.0 {
+                h.push(err);
+            }
+            let (sum, err) = fast_two_sum(product, sum);
+            if err != 0.0 {
+                h.push(err);
+            }
+            q = sum;
+        }
+        if q != 0.0 {
+            h.push(q);
+        }
+        Self(h)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        other
+            .0
+            .iter()
+            .fold(Self(vec![]), |sum, f| sum.add(&self.scale(*f)))
+    }
+
+    /// The largest component carries the sign of the whole expansion, and approximates
+    /// its value.
+    fn estimate(&self) -> f64 {
+        self.0.iter().sum()
+    }
+}
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    let b_round = b - b_virtual;
+    let a_round = a - a_virtual;
+    (x, a_round + b_round)
+}
+
+/// Requires |a| >= |b|.
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    (x, b - b_virtual)
+}
+
+fn two_diff(a: f64, b: f64) -> (f64, f64) {
+    two_sum(a, -b)
+}
+
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    // A fused multiply-add rounds only once, which recovers the rounding error exactly.
+    (x, a.mul_add(b, -x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orient2d() {
+        assert!(orient2d([0.0, 0.0], [1.0, 0.0], [0.0, 1.0]) > 0.0);
+        assert!(orient2d([0.0, 0.0], [0.0, 1.0], [1.0, 0.0]) < 0.0);
+        assert_eq!(orient2d([0.0, 0.0], [1.0, 1.0], [2.0, 2.0]), 0.0);
+
+        // Kettner et al., "Classroom examples of robustness problems in geometric
+        // computations": plain floating point gets the orientation of points near the
+        // line y = x wrong in a fractal pattern.
+        let b = [12.0, 12.0];
+        let c = [24.0, 24.0];
+        for i in 0..256 {
+            for j in 0..256 {
+                let a = [0.5 + i as f64 * f64::EPSILON, 0.5 + j as f64 * f64::EPSILON];
+                let expected = i.cmp(&j).reverse();
+                let actual = orient2d(a, b, c).partial_cmp(&0.0).unwrap();
+                assert_eq!(actual, expected, "{a:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_orient3d() {
+        let a = [0.0, 0.0, 0.0];
+        let b = [1.0, 0.0, 0.0];
+        let c = [0.0, 1.0, 0.0];
+        assert!(orient3d(a, b, c, [0.0, 0.0, -1.0]) > 0.0);
+        assert!(orient3d(a, b, c, [0.0, 0.0, 1.0]) < 0.0);
+        assert_eq!(orient3d(a, b, c, [5.0, 7.0, 0.0]), 0.0);
+
+        // A plane which no f64 other than its own points lie on, leaving the nearest
+        // points only an ulp away.
+        let a = [0.1, 0.2, 0.3];
+        let b = [1.1, 0.2, 0.7];
+        let c = [0.1, 1.2, 0.5];
+        let d = [2.1, 2.2, 1.5];
+        let on = orient3d(a, b, c, d);
+        let above = orient3d(a, b, c, [d[0], d[1], f64::from_bits(d[2].to_bits() + 1)]);
+        let below = orient3d(a, b, c, [d[0], d[1], f64::from_bits(d[2].to_bits() - 1)]);
+        assert!(above < on && on < below, "{above} {on} {below}");
+    }
+
+    #[test]
+    fn test_incircle() {
+        let a = [1.0, 0.0];
+        let b = [0.0, 1.0];
+        let c = [-1.0, 0.0];
+        assert!(incircle(a, b, c, [0.0, 0.0]) > 0.0);
+        assert!(incircle(a, b, c, [2.0, 0.0]) < 0.0);
+        assert_eq!(incircle(a, b, c, [0.0, -1.0]), 0.0);
+
+        // 3-4-5 triangle, so (3, 4) lies exactly on the circle of radius 5.
+        let a = [5.0, 0.0];
+        let b = [0.0, 5.0];
+        let c = [-5.0, 0.0];
+        assert_eq!(incircle(a, b, c, [3.0, 4.0]), 0.0);
+        // Nudge it by a single ulp.
+        let below = f64::from_bits(4f64.to_bits() - 1);
+        let above = f64::from_bits(4f64.to_bits() + 1);
+        assert!(incircle(a, b, c, [3.0, below]) > 0.0);
+        assert!(incircle(a, b, c, [3.0, above]) < 0.0);
+    }
+
+    #[test]
+    fn test_incircle_sos() {
+        // The corners of a square are cocircular, yet exactly one of the diagonals
+        // must win, no matter which triangle is asked.
+        let a = [0.0, 0.0];
+        let b = [1.0, 0.0];
+        let c = [1.0, 1.0];
+        let d = [0.0, 1.0];
+        assert_eq!(incircle(a, b, c, d), 0.0);
+        let abc = incircle_sos(a, b, c, d) == Ordering::Greater;
+        let cda = incircle_sos(c, d, a, b) == Ordering::Greater;
+        let bcd = incircle_sos(b, c, d, a) == Ordering::Greater;
+        let dab = incircle_sos(d, a, b, c) == Ordering::Greater;
+        // The diagonal a-c is Delaunay iff neither triangle on it contains the fourth
+        // point.
+        assert_eq!(abc, cda);
+        assert_eq!(bcd, dab);
+        assert_ne!(abc, bcd);
+    }
+
+    #[test]
+    fn test_insphere() {
+        let a = [3.0, 0.0, 0.0];
+        let b = [0.0, 3.0, 0.0];
+        let c = [0.0, 0.0, 3.0];
+        let d = [-3.0, 0.0, 0.0];
+        assert!(orient3d(a, b, c, d) > 0.0);
+        assert!(insphere(a, b, c, d, [0.0, 0.0, 0.0]) > 0.0);
+        assert!(insphere(a, b, c, d, [4.0, 0.0, 0.0]) < 0.0);
+        assert!(insphere(b, a, c, d, [0.0, 0.0, 0.0]) < 0.0);
+
+        // (1, 2, 2) lies exactly on the sphere of radius 3.
+        assert_eq!(insphere(a, b, c, d, [1.0, 2.0, 2.0]), 0.0);
+        let below = f64::from_bits(2f64.to_bits() - 1);
+        let above = f64::from_bits(2f64.to_bits() + 1);
+        assert!(insphere(a, b, c, d, [1.0, 2.0, below]) > 0.0);
+        assert!(insphere(a, b, c, d, [1.0, 2.0, above]) < 0.0);
+    }
+
+    #[test]
+    fn test_insphere_sos() {
+        // The corners of a cube are cospherical. Perturbing them has to keep the
+        // determinant antisymmetric, like the unperturbed one.
+        let corners = (0..8)
+            .map(|i| [i & 1, i >> 1 & 1, i >> 2].map(|c| c as f64))
+            .collect::<Vec<_>>();
+        for [a, b, c, d, e] in [[0, 1, 2, 4, 7], [1, 2, 4, 7, 3], [6, 5, 3, 0, 1]] {
+            let [a, b, c, d, e] = [a, b, c, d, e].map(|i| corners[i]);
+            assert_eq!(insphere(a, b, c, d, e), 0.0);
+            let sos = insphere_sos(a, b, c, d, e);
+            assert_ne!(sos, Ordering::Equal);
+            assert_eq!(insphere_sos(b, a, c, d, e), sos.reverse());
+            assert_eq!(insphere_sos(a, b, c, e, d), sos.reverse());
+            assert_eq!(insphere_sos(e, b, c, d, a), sos.reverse());
+            assert_eq!(insphere_sos(b, c, d, e, a), sos);
+        }
+    }
+
+    #[test]
+    fn test_expansion() {
+        let big = 2f64.powi(60);
+        let sum = Expansion::diff(big, -1.0).sub(&Expansion::diff(big, 0.0));
+        assert_eq!(sum.estimate(), 1.0);
+        let product = Expansion::diff(big, -1.0).mul(&Expansion::diff(big, 1.0));
+        // (2^60 + 1)(2^60 - 1) = 2^120 - 1
+        assert_eq!(
+            product
+                .sub(&Expansion::diff(big, 0.0).scale(big))
+                .estimate(),
+            -1.0
+        );
+    }
+}